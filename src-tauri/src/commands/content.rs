@@ -1,10 +1,20 @@
 use super::map_app_result;
 use crate::content::{
-    delete_instance_content_impl, list_instance_content_impl, list_instance_reports_impl,
-    open_instance_content_folder_impl, read_instance_report_impl, toggle_instance_content_impl,
+    analyze_instance_report_impl, apply_mod_set_impl, check_mod_set_impl, create_mod_set_impl,
+    delete_instance_content_impl, delete_mod_set_impl, end_mod_bisect_impl, get_mod_bisect_impl,
+    get_mod_icon_impl, import_instance_content_impl, list_duplicate_mods_impl,
+    list_instance_content_impl, list_instance_reports_impl, list_mod_sets_impl,
+    list_orphaned_mods_impl, mark_mod_bisect_impl, open_instance_content_folder_impl,
+    preview_content_dependencies_impl, read_instance_report_impl, resolve_duplicate_mods_impl,
+    search_instance_logs_impl, set_content_pinned_impl, start_mod_bisect_impl,
+    toggle_instance_content_impl,
 };
 use crate::metrics::get_runtime_metrics_impl;
-use crate::models::{InstanceContentItem, InstanceLogEntry, RuntimeMetrics};
+use crate::models::{
+    ContentDependencyReport, ContentImportRequest, ContentImportResult, CrashAnalysis,
+    DuplicateModGroup, DuplicateResolveResult, InstanceContentItem, InstanceLogEntry,
+    LogSearchPage, LogSearchQuery, ModBisectSession, ModSet, ModSetApplyResult, RuntimeMetrics,
+};
 
 #[tauri::command]
 pub async fn list_instance_content(
@@ -15,6 +25,15 @@ pub async fn list_instance_content(
     map_app_result(list_instance_content_impl(&app, instance_id, kind).await)
 }
 
+#[tauri::command]
+pub async fn get_mod_icon(
+    app: tauri::AppHandle,
+    instance_id: String,
+    file_name: String,
+) -> Result<Option<String>, String> {
+    map_app_result(get_mod_icon_impl(&app, instance_id, file_name).await)
+}
+
 #[tauri::command]
 pub async fn toggle_instance_content(
     app: tauri::AppHandle,
@@ -22,8 +41,21 @@ pub async fn toggle_instance_content(
     kind: String,
     file_name: String,
     enabled: bool,
-) -> Result<(), String> {
-    map_app_result(toggle_instance_content_impl(&app, instance_id, kind, file_name, enabled).await)
+    cascade: Option<bool>,
+    remove_orphans: Option<bool>,
+) -> Result<Vec<String>, String> {
+    map_app_result(
+        toggle_instance_content_impl(
+            &app,
+            instance_id,
+            kind,
+            file_name,
+            enabled,
+            cascade,
+            remove_orphans,
+        )
+        .await,
+    )
 }
 
 #[tauri::command]
@@ -32,8 +64,150 @@ pub async fn delete_instance_content(
     instance_id: String,
     kind: String,
     file_name: String,
+    cascade: Option<bool>,
+    remove_orphans: Option<bool>,
+) -> Result<Vec<String>, String> {
+    map_app_result(
+        delete_instance_content_impl(&app, instance_id, kind, file_name, cascade, remove_orphans)
+            .await,
+    )
+}
+
+#[tauri::command]
+pub async fn import_instance_content(
+    app: tauri::AppHandle,
+    instance_id: String,
+    request: ContentImportRequest,
+) -> Result<ContentImportResult, String> {
+    map_app_result(import_instance_content_impl(&app, instance_id, request).await)
+}
+
+#[tauri::command]
+pub async fn pin_instance_content(
+    app: tauri::AppHandle,
+    instance_id: String,
+    kind: String,
+    file_name: String,
+) -> Result<(), String> {
+    map_app_result(set_content_pinned_impl(&app, &instance_id, &kind, &file_name, true).await)
+}
+
+#[tauri::command]
+pub async fn unpin_instance_content(
+    app: tauri::AppHandle,
+    instance_id: String,
+    kind: String,
+    file_name: String,
 ) -> Result<(), String> {
-    map_app_result(delete_instance_content_impl(&app, instance_id, kind, file_name).await)
+    map_app_result(set_content_pinned_impl(&app, &instance_id, &kind, &file_name, false).await)
+}
+
+#[tauri::command]
+pub async fn preview_content_dependencies(
+    app: tauri::AppHandle,
+    instance_id: String,
+    file_name: String,
+) -> Result<ContentDependencyReport, String> {
+    map_app_result(preview_content_dependencies_impl(&app, instance_id, file_name).await)
+}
+
+#[tauri::command]
+pub async fn list_orphaned_mods(
+    app: tauri::AppHandle,
+    instance_id: String,
+) -> Result<Vec<String>, String> {
+    map_app_result(list_orphaned_mods_impl(&app, instance_id).await)
+}
+
+#[tauri::command]
+pub async fn list_duplicate_mods(
+    app: tauri::AppHandle,
+    instance_id: String,
+) -> Result<Vec<DuplicateModGroup>, String> {
+    map_app_result(list_duplicate_mods_impl(&app, instance_id).await)
+}
+
+#[tauri::command]
+pub async fn resolve_duplicate_mods(
+    app: tauri::AppHandle,
+    instance_id: String,
+) -> Result<DuplicateResolveResult, String> {
+    map_app_result(resolve_duplicate_mods_impl(&app, instance_id).await)
+}
+
+#[tauri::command]
+pub async fn list_mod_sets(
+    app: tauri::AppHandle,
+    instance_id: String,
+) -> Result<Vec<ModSet>, String> {
+    map_app_result(list_mod_sets_impl(&app, instance_id).await)
+}
+
+#[tauri::command]
+pub async fn create_mod_set(
+    app: tauri::AppHandle,
+    instance_id: String,
+    name: String,
+) -> Result<ModSet, String> {
+    map_app_result(create_mod_set_impl(&app, instance_id, name).await)
+}
+
+#[tauri::command]
+pub async fn check_mod_set(
+    app: tauri::AppHandle,
+    instance_id: String,
+    name: String,
+) -> Result<Vec<String>, String> {
+    map_app_result(check_mod_set_impl(&app, instance_id, name).await)
+}
+
+#[tauri::command]
+pub async fn apply_mod_set(
+    app: tauri::AppHandle,
+    instance_id: String,
+    name: String,
+    force: Option<bool>,
+) -> Result<ModSetApplyResult, String> {
+    map_app_result(apply_mod_set_impl(&app, instance_id, name, force).await)
+}
+
+#[tauri::command]
+pub async fn delete_mod_set(
+    app: tauri::AppHandle,
+    instance_id: String,
+    name: String,
+) -> Result<(), String> {
+    map_app_result(delete_mod_set_impl(&app, instance_id, name).await)
+}
+
+#[tauri::command]
+pub async fn start_mod_bisect(
+    app: tauri::AppHandle,
+    instance_id: String,
+) -> Result<ModBisectSession, String> {
+    map_app_result(start_mod_bisect_impl(&app, instance_id).await)
+}
+
+#[tauri::command]
+pub async fn get_mod_bisect(
+    app: tauri::AppHandle,
+    instance_id: String,
+) -> Result<Option<ModBisectSession>, String> {
+    map_app_result(get_mod_bisect_impl(&app, instance_id).await)
+}
+
+#[tauri::command]
+pub async fn mark_mod_bisect(
+    app: tauri::AppHandle,
+    instance_id: String,
+    crashed: bool,
+) -> Result<ModBisectSession, String> {
+    map_app_result(mark_mod_bisect_impl(&app, instance_id, crashed).await)
+}
+
+#[tauri::command]
+pub async fn end_mod_bisect(app: tauri::AppHandle, instance_id: String) -> Result<(), String> {
+    map_app_result(end_mod_bisect_impl(&app, instance_id).await)
 }
 
 #[tauri::command]
@@ -63,6 +237,25 @@ pub async fn read_instance_report(
     map_app_result(read_instance_report_impl(&app, instance_id, kind, name).await)
 }
 
+#[tauri::command]
+pub async fn analyze_instance_report(
+    app: tauri::AppHandle,
+    instance_id: String,
+    kind: Option<String>,
+    name: Option<String>,
+) -> Result<CrashAnalysis, String> {
+    map_app_result(analyze_instance_report_impl(&app, instance_id, kind, name).await)
+}
+
+#[tauri::command]
+pub async fn search_instance_logs(
+    app: tauri::AppHandle,
+    instance_id: String,
+    query: LogSearchQuery,
+) -> Result<LogSearchPage, String> {
+    map_app_result(search_instance_logs_impl(&app, instance_id, query).await)
+}
+
 #[tauri::command]
 pub fn get_runtime_metrics(pid: Option<u32>) -> Result<RuntimeMetrics, String> {
     map_app_result(get_runtime_metrics_impl(pid))
@@ -1,5 +1,9 @@
 use super::map_app_result;
-use crate::diagnostics::{generate_diagnostic_report_impl, upload_diagnostic_report_impl};
+use crate::diagnostics::{
+    generate_diagnostic_report_impl, get_redaction_config_impl, set_redaction_config_impl,
+    upload_diagnostic_report_impl,
+};
+use crate::models::RedactionConfig;
 use crate::repair::repair_instance_impl;
 use crate::state::AppState;
 use crate::utils::get_launcher_dir;
@@ -51,3 +55,16 @@ pub async fn upload_diagnostic_report(
 ) -> Result<String, String> {
     map_app_result(upload_diagnostic_report_impl(&app, report_path, instance_id).await)
 }
+
+#[tauri::command]
+pub async fn get_redaction_config(app: tauri::AppHandle) -> Result<RedactionConfig, String> {
+    map_app_result(get_redaction_config_impl(&app).await)
+}
+
+#[tauri::command]
+pub async fn set_redaction_config(
+    app: tauri::AppHandle,
+    config: RedactionConfig,
+) -> Result<RedactionConfig, String> {
+    map_app_result(set_redaction_config_impl(&app, config).await)
+}
@@ -1,9 +1,21 @@
 use super::map_app_result;
 use crate::instances::{
-    create_instance_impl, delete_instance_impl, list_instances_impl, open_instance_folder_impl,
-    update_instance_impl,
+    apply_instance_upgrade_impl, create_instance_impl, delete_instance_impl,
+    duplicate_instance_impl, get_trash_config_impl, list_instances_impl,
+    list_trashed_instances_impl, open_instance_folder_impl, preview_instance_upgrade_impl,
+    purge_trashed_instances_impl, restore_trashed_instance_impl, set_instance_allow_multiple_impl,
+    set_instance_shared_folders_impl, set_trash_config_impl, update_instance_impl,
 };
-use crate::models::{InstanceCreateRequest, InstanceSummary, InstanceUpdateRequest};
+use crate::launcher::get_effective_settings_impl;
+use crate::models::{
+    EffectiveGameSettings, GameSettings, InstanceCreateRequest, InstanceDuplicateRequest,
+    InstancePlaytime, InstanceSummary, InstanceUpdateRequest, InstanceUpgradePreview,
+    InstanceUpgradeRequest, TrashConfig, TrashedInstance,
+};
+use crate::playtime::get_instance_playtime_impl;
+use crate::prism::{export_prism_instance_impl, import_prism_instance_impl};
+use crate::state::AppState;
+use tauri::State;
 
 #[tauri::command]
 pub async fn list_instances(app: tauri::AppHandle) -> Result<Vec<InstanceSummary>, String> {
@@ -27,12 +39,141 @@ pub async fn update_instance(
     map_app_result(update_instance_impl(&app, instance_id, req).await)
 }
 
+#[tauri::command]
+pub async fn duplicate_instance(
+    app: tauri::AppHandle,
+    instance_id: String,
+    req: InstanceDuplicateRequest,
+) -> Result<InstanceSummary, String> {
+    map_app_result(duplicate_instance_impl(&app, instance_id, req).await)
+}
+
+#[tauri::command]
+pub async fn set_instance_shared_folders(
+    app: tauri::AppHandle,
+    instance_id: String,
+    folders: Vec<String>,
+) -> Result<InstanceSummary, String> {
+    map_app_result(set_instance_shared_folders_impl(&app, instance_id, folders).await)
+}
+
+#[tauri::command]
+pub async fn preview_instance_upgrade(
+    app: tauri::AppHandle,
+    instance_id: String,
+    req: InstanceUpgradeRequest,
+) -> Result<InstanceUpgradePreview, String> {
+    map_app_result(preview_instance_upgrade_impl(&app, instance_id, req).await)
+}
+
+#[tauri::command]
+pub async fn apply_instance_upgrade(
+    app: tauri::AppHandle,
+    instance_id: String,
+    req: InstanceUpgradeRequest,
+    state: State<'_, AppState>,
+) -> Result<InstanceSummary, String> {
+    map_app_result(
+        apply_instance_upgrade_impl(
+            &app,
+            instance_id,
+            req,
+            &state.manifest_cache,
+            &state.metadata_cache,
+        )
+        .await,
+    )
+}
+
+#[tauri::command]
+pub async fn import_prism_instance(
+    app: tauri::AppHandle,
+    path: String,
+    name: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<InstanceSummary, String> {
+    map_app_result(
+        import_prism_instance_impl(&app, path, name, &state.manifest_cache, &state.metadata_cache)
+            .await,
+    )
+}
+
+#[tauri::command]
+pub async fn export_prism_instance(
+    app: tauri::AppHandle,
+    instance_id: String,
+    dest_path: Option<String>,
+) -> Result<String, String> {
+    map_app_result(export_prism_instance_impl(&app, instance_id, dest_path).await)
+}
+
 #[tauri::command]
 pub async fn delete_instance(app: tauri::AppHandle, instance_id: String) -> Result<(), String> {
     map_app_result(delete_instance_impl(&app, instance_id).await)
 }
 
+#[tauri::command]
+pub async fn list_trashed_instances(app: tauri::AppHandle) -> Result<Vec<TrashedInstance>, String> {
+    map_app_result(list_trashed_instances_impl(&app).await)
+}
+
+#[tauri::command]
+pub async fn restore_trashed_instance(
+    app: tauri::AppHandle,
+    trash_id: String,
+) -> Result<InstanceSummary, String> {
+    map_app_result(restore_trashed_instance_impl(&app, trash_id).await)
+}
+
+#[tauri::command]
+pub async fn purge_trashed_instances(
+    app: tauri::AppHandle,
+    trash_id: Option<String>,
+) -> Result<u32, String> {
+    map_app_result(purge_trashed_instances_impl(&app, trash_id).await)
+}
+
+#[tauri::command]
+pub async fn get_trash_config(app: tauri::AppHandle) -> Result<TrashConfig, String> {
+    map_app_result(get_trash_config_impl(&app).await)
+}
+
+#[tauri::command]
+pub async fn set_trash_config(
+    app: tauri::AppHandle,
+    config: TrashConfig,
+) -> Result<TrashConfig, String> {
+    map_app_result(set_trash_config_impl(&app, config).await)
+}
+
+#[tauri::command]
+pub async fn set_instance_allow_multiple(
+    app: tauri::AppHandle,
+    instance_id: String,
+    allow: bool,
+) -> Result<InstanceSummary, String> {
+    map_app_result(set_instance_allow_multiple_impl(&app, &instance_id, allow).await)
+}
+
 #[tauri::command]
 pub fn open_instance_folder(app: tauri::AppHandle, instance_id: String) -> Result<(), String> {
     map_app_result(open_instance_folder_impl(&app, instance_id))
 }
+
+#[tauri::command]
+pub async fn get_effective_settings(
+    app: tauri::AppHandle,
+    instance_id: Option<String>,
+    settings: Option<GameSettings>,
+) -> Result<EffectiveGameSettings, String> {
+    map_app_result(get_effective_settings_impl(&app, instance_id, settings).await)
+}
+
+#[tauri::command]
+pub async fn get_instance_playtime(
+    app: tauri::AppHandle,
+    instance_id: String,
+    limit: Option<usize>,
+) -> Result<InstancePlaytime, String> {
+    map_app_result(get_instance_playtime_impl(&app, instance_id, limit).await)
+}
@@ -9,6 +9,7 @@ pub mod instances;
 pub mod modrinth;
 pub mod skins;
 pub mod system;
+pub mod templates;
 pub mod versions;
 pub mod worlds;
 
@@ -22,6 +23,7 @@ pub use instances::*;
 pub use modrinth::*;
 pub use skins::*;
 pub use system::*;
+pub use templates::*;
 pub use versions::*;
 pub use worlds::*;
 
@@ -1,11 +1,12 @@
 use super::map_app_result;
 use crate::curseforge::curseforge_search_impl;
 use crate::models::{
-    CurseForgeSearchResponse, InstanceSummary, ModrinthProject, ModrinthSearchResponse,
-    ModrinthVersion,
+    ContentIdentifyResult, ContentUpdateInfo, ContentUpdateResult, CurseForgeSearchResponse,
+    InstanceSummary, ModrinthProject, ModrinthSearchResponse, ModrinthVersion,
 };
 use crate::modrinth::{
-    apply_optimization_pack_impl, export_modpack_mrpack_impl, import_modpack_mrpack_impl,
+    apply_content_updates_impl, apply_optimization_pack_impl, check_content_updates_impl,
+    export_modpack_mrpack_impl, identify_instance_content_impl, import_modpack_mrpack_impl,
     modrinth_get_project_impl, modrinth_install_datapack_impl, modrinth_install_modpack_impl,
     modrinth_install_version_impl, modrinth_list_versions_impl, modrinth_search_impl,
 };
@@ -102,6 +103,33 @@ pub async fn modrinth_install_version(
     }
 }
 
+#[tauri::command]
+pub async fn identify_instance_content(
+    app: tauri::AppHandle,
+    instance_id: String,
+    kind: Option<String>,
+) -> Result<ContentIdentifyResult, String> {
+    map_app_result(identify_instance_content_impl(&app, instance_id, kind).await)
+}
+
+#[tauri::command]
+pub async fn check_content_updates(
+    app: tauri::AppHandle,
+    instance_id: String,
+    kind: Option<String>,
+) -> Result<Vec<ContentUpdateInfo>, String> {
+    map_app_result(check_content_updates_impl(&app, instance_id, kind).await)
+}
+
+#[tauri::command]
+pub async fn apply_content_updates(
+    app: tauri::AppHandle,
+    instance_id: String,
+    file_names: Vec<String>,
+) -> Result<ContentUpdateResult, String> {
+    map_app_result(apply_content_updates_impl(&app, instance_id, file_names).await)
+}
+
 #[tauri::command]
 pub async fn modrinth_install_modpack(
     app: tauri::AppHandle,
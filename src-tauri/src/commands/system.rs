@@ -1,9 +1,10 @@
+use super::map_app_result;
 use crate::diagnostics::{
     generate_diagnostic_report_for_instance_impl, upload_diagnostic_report_impl,
 };
-use crate::instances::touch_instance_impl;
-use crate::launcher::launch_game_impl;
-use crate::models::{GameSettings, SystemJava};
+use crate::instances::{get_instance_impl, touch_instance_impl};
+use crate::launcher::{launch_game_impl, stop_game_impl};
+use crate::models::{GameLogLine, GameSettings, RunningGame, SystemJava};
 use crate::repair::repair_instance_impl;
 use crate::state::AppState;
 use crate::utils::{get_launcher_dir, hide_background_window};
@@ -76,6 +77,35 @@ fn support_auto_upload_enabled() -> bool {
     matches!(value.as_str(), "1" | "true" | "yes" | "on")
 }
 
+/// Lineas guardadas en memoria para que una consola recien abierta se ponga al dia.
+#[tauri::command]
+pub fn get_game_log(
+    instance_id: Option<String>,
+    since: Option<u64>,
+    state: State<'_, AppState>,
+) -> Result<Vec<GameLogLine>, String> {
+    Ok(state.game_logs.snapshot(instance_id.as_deref(), since))
+}
+
+#[tauri::command]
+pub fn clear_game_log(
+    instance_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    state.game_logs.clear(instance_id.as_deref());
+    Ok(())
+}
+
+#[tauri::command]
+pub fn list_running_games(state: State<'_, AppState>) -> Result<Vec<RunningGame>, String> {
+    Ok(state.processes.list())
+}
+
+#[tauri::command]
+pub async fn stop_game(app: tauri::AppHandle, pid: u32) -> Result<String, String> {
+    map_app_result(stop_game_impl(&app, pid).await)
+}
+
 #[tauri::command]
 pub async fn launch_game(
     app: tauri::AppHandle,
@@ -86,6 +116,15 @@ pub async fn launch_game(
     state: State<'_, AppState>,
 ) -> Result<String, String> {
     let version_for_report = version_id.clone();
+    // Se bloquea antes del auto-repair: repararla con el juego abierto romperia la partida.
+    let _reservation = match instance_id.as_deref() {
+        Some(id) => {
+            let allow_multiple =
+                get_instance_impl(&app, id).await.map(|inst| inst.allow_multiple).unwrap_or(false);
+            Some(state.processes.begin_launch(id, allow_multiple).map_err(|e| e.to_string())?)
+        }
+        None => None,
+    };
     if let Some(id) = &instance_id {
         let _ = touch_instance_impl(&app, id).await;
     }
@@ -0,0 +1,53 @@
+use super::map_app_result;
+use crate::models::{
+    InstanceTemplate, TemplateCreateRequest, TemplateCreateResult, TemplateSaveRequest,
+};
+use crate::state::AppState;
+use crate::templates::{
+    create_instance_from_template_impl, delete_instance_template_impl,
+    list_instance_templates_impl, save_instance_template_impl,
+};
+use tauri::State;
+
+#[tauri::command]
+pub async fn list_instance_templates(
+    app: tauri::AppHandle,
+) -> Result<Vec<InstanceTemplate>, String> {
+    map_app_result(list_instance_templates_impl(&app).await)
+}
+
+#[tauri::command]
+pub async fn save_instance_template(
+    app: tauri::AppHandle,
+    instance_id: String,
+    req: TemplateSaveRequest,
+) -> Result<InstanceTemplate, String> {
+    map_app_result(save_instance_template_impl(&app, instance_id, req).await)
+}
+
+#[tauri::command]
+pub async fn delete_instance_template(
+    app: tauri::AppHandle,
+    template_id: String,
+) -> Result<(), String> {
+    map_app_result(delete_instance_template_impl(&app, template_id).await)
+}
+
+#[tauri::command]
+pub async fn create_instance_from_template(
+    app: tauri::AppHandle,
+    template_id: String,
+    req: TemplateCreateRequest,
+    state: State<'_, AppState>,
+) -> Result<TemplateCreateResult, String> {
+    map_app_result(
+        create_instance_from_template_impl(
+            &app,
+            template_id,
+            req,
+            &state.manifest_cache,
+            &state.metadata_cache,
+        )
+        .await,
+    )
+}
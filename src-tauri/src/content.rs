@@ -1,11 +1,40 @@
 use crate::instances::refresh_instance_mods_cache;
-use crate::models::{InstanceContentItem, InstanceLogEntry, ModMetadataEntry};
-use crate::utils::get_launcher_dir;
+use crate::models::{
+    ContentDependencyReport, InstanceContentItem, InstanceLogEntry, ModMetadataEntry,
+};
+use crate::utils::{append_action_log, get_launcher_dir};
 use std::path::{Path, PathBuf};
 use tauri::AppHandle;
 use tokio::fs as tokio_fs;
 
 use crate::error::AppResult;
+
+mod bisect;
+mod crash;
+mod dependencies;
+mod duplicates;
+mod import;
+mod log_search;
+mod mod_info;
+mod mod_sets;
+mod pinning;
+
+pub use bisect::{
+    end_mod_bisect_impl, get_mod_bisect_impl, mark_mod_bisect_impl, start_mod_bisect_impl,
+};
+pub use crash::analyze_instance_report_impl;
+pub(crate) use duplicates::{describe_duplicates, find_duplicate_mods};
+pub use duplicates::{list_duplicate_mods_impl, resolve_duplicate_mods_impl};
+pub use import::import_instance_content_impl;
+pub use log_search::search_instance_logs_impl;
+pub(crate) use mod_info::read_mod_info;
+pub use mod_sets::{
+    apply_mod_set_impl, check_mod_set_impl, create_mod_set_impl, delete_mod_set_impl,
+    list_mod_sets_impl,
+};
+pub use pinning::set_content_pinned_impl;
+pub(crate) use pinning::{find_pinned, pinned_mod_ids, pinned_paths};
+
 fn instance_dir(app: &AppHandle, instance_id: &str) -> PathBuf {
     get_launcher_dir(app).join("instances").join(instance_id)
 }
@@ -28,7 +57,10 @@ fn mods_metadata_path(app: &AppHandle, instance_id: &str) -> PathBuf {
     metadata_dir(app, instance_id).join("mods.json")
 }
 
-async fn load_mods_metadata(app: &AppHandle, instance_id: &str) -> Vec<ModMetadataEntry> {
+pub(crate) async fn load_mods_metadata(
+    app: &AppHandle,
+    instance_id: &str,
+) -> Vec<ModMetadataEntry> {
     let path = mods_metadata_path(app, instance_id);
     if !path.exists() {
         return Vec::new();
@@ -41,14 +73,18 @@ async fn load_mods_metadata(app: &AppHandle, instance_id: &str) -> Vec<ModMetada
     Vec::new()
 }
 
-fn metadata_kind_matches(entry_kind: &Option<String>, kind: &str) -> bool {
+pub(crate) fn metadata_kind_matches(entry_kind: &Option<String>, kind: &str) -> bool {
     match entry_kind.as_deref() {
         Some(k) => k == kind,
         None => kind == "mods",
     }
 }
 
-async fn save_mods_metadata(app: &AppHandle, instance_id: &str, entries: &[ModMetadataEntry]) {
+pub(crate) async fn save_mods_metadata(
+    app: &AppHandle,
+    instance_id: &str,
+    entries: &[ModMetadataEntry],
+) {
     let path = mods_metadata_path(app, instance_id);
     if let Some(parent) = path.parent() {
         let _ = tokio_fs::create_dir_all(parent).await;
@@ -58,7 +94,7 @@ async fn save_mods_metadata(app: &AppHandle, instance_id: &str, entries: &[ModMe
     }
 }
 
-fn strip_disabled(name: &str) -> (String, bool) {
+pub(crate) fn strip_disabled(name: &str) -> (String, bool) {
     if name.ends_with(".disabled") {
         (name.trim_end_matches(".disabled").to_string(), false)
     } else {
@@ -131,6 +167,7 @@ pub async fn list_instance_content_impl(
         let mut source = None;
         let mut project_id = None;
         let mut version_id = None;
+        let mut pinned = false;
         let lookup = if enabled { file_name.clone() } else { display_name.clone() };
         if let Some(entry_meta) = metadata
             .iter()
@@ -144,6 +181,7 @@ pub async fn list_instance_content_impl(
             source = entry_meta.source.clone();
             project_id = entry_meta.project_id.clone();
             version_id = entry_meta.version_id.clone();
+            pinned = entry_meta.pinned;
             if kind == "mods" {
                 if let Some(v) = &entry_meta.version_id {
                     if let Some(list) = required_by_map.get(v) {
@@ -169,20 +207,214 @@ pub async fn list_instance_content_impl(
             source,
             project_id,
             version_id,
+            mod_info: None,
+            pinned,
         });
     }
 
-    out.sort_by(|a, b| b.modified.cmp(&a.modified));
+    if kind == "mods" {
+        attach_mod_info(app, &instance_id, &dir, &mut out).await;
+    }
+    out.sort_by_key(|entry| std::cmp::Reverse(entry.modified));
     Ok(out)
 }
 
+fn mod_info_cache_path(app: &AppHandle, instance_id: &str) -> PathBuf {
+    metadata_dir(app, instance_id).join(mod_info::CACHE_FILE)
+}
+
+// Solo se vuelven a abrir los jars cuyo tamano o mtime cambio desde el ultimo listado.
+async fn attach_mod_info(
+    app: &AppHandle,
+    instance_id: &str,
+    dir: &Path,
+    items: &mut [InstanceContentItem],
+) {
+    let cache_path = mod_info_cache_path(app, instance_id);
+    let cache = match tokio_fs::read_to_string(&cache_path).await {
+        Ok(raw) => mod_info::parse_cache(&raw),
+        Err(_) => mod_info::ModInfoCache::new(),
+    };
+    let files: Vec<(String, u64, i64)> =
+        items.iter().map(|i| (i.file_name.clone(), i.size, i.modified)).collect();
+    let dir = dir.to_path_buf();
+    let result = tokio::task::spawn_blocking(move || {
+        let mut fresh = mod_info::ModInfoCache::new();
+        let mut changed = false;
+        for (file_name, size, modified) in files {
+            let (clean_name, _) = strip_disabled(&file_name);
+            let cached = cache.get(&clean_name).filter(|c| c.is_fresh(size, modified));
+            let entry = match cached {
+                Some(entry) => entry.clone(),
+                None => {
+                    changed = true;
+                    mod_info::CachedModInfo::new(
+                        size,
+                        modified,
+                        mod_info::read_mod_info(&dir.join(&file_name)),
+                    )
+                }
+            };
+            fresh.insert(clean_name, entry);
+        }
+        // Tambien cuenta como cambio que un jar haya desaparecido.
+        let changed = changed || fresh.len() != cache.len();
+        (fresh, changed)
+    })
+    .await;
+    let (cache, changed) = match result {
+        Ok(result) => result,
+        Err(_) => return,
+    };
+    for item in items.iter_mut() {
+        let (clean_name, _) = strip_disabled(&item.file_name);
+        item.mod_info = cache.get(&clean_name).and_then(|c| c.info.clone());
+    }
+    if changed {
+        if let Some(parent) = cache_path.parent() {
+            let _ = tokio_fs::create_dir_all(parent).await;
+        }
+        if let Ok(raw) = serde_json::to_string(&cache) {
+            let _ = tokio_fs::write(&cache_path, raw).await;
+        }
+    }
+}
+
+/// Icono de un mod como data URL. Va aparte del listado para no guardar ni enviar cada
+/// imagen en `mod_info.cache.json`.
+pub async fn get_mod_icon_impl(
+    app: &AppHandle,
+    instance_id: String,
+    file_name: String,
+) -> AppResult<Option<String>> {
+    let dir = instance_kind_dir(app, &instance_id, "mods")?;
+    let (clean, _) = strip_disabled(&file_name);
+    if clean.contains('/') || clean.contains('\\') || clean.contains("..") {
+        return Err("Nombre de archivo invalido".to_string().into());
+    }
+    let path = [dir.join(&clean), dir.join(format!("{}.disabled", clean))]
+        .into_iter()
+        .find(|p| p.is_file())
+        .ok_or_else(|| crate::error::AppError::Message("Archivo no encontrado".to_string()))?;
+    tokio::task::spawn_blocking(move || {
+        let icon_path = mod_info::read_mod_info(&path)?.icon_path?;
+        mod_info::read_mod_icon(&path, &icon_path)
+    })
+    .await
+    .map_err(|e| crate::error::AppError::Message(e.to_string()))
+}
+
+async fn load_mod_graph(
+    app: &AppHandle,
+    instance_id: &str,
+) -> AppResult<Vec<dependencies::ModNode>> {
+    let dir = instance_kind_dir(app, instance_id, "mods")?;
+    let metadata = load_mods_metadata(app, instance_id).await;
+    tokio::task::spawn_blocking(move || dependencies::mod_graph(&dir, &metadata))
+        .await
+        .map_err(|e| crate::error::AppError::Message(e.to_string()))
+}
+
+pub async fn preview_content_dependencies_impl(
+    app: &AppHandle,
+    instance_id: String,
+    file_name: String,
+) -> AppResult<ContentDependencyReport> {
+    let nodes = load_mod_graph(app, &instance_id).await?;
+    let idx = dependencies::find_node(&nodes, &file_name)
+        .ok_or_else(|| crate::error::AppError::Message("Archivo no encontrado".to_string()))?;
+    let dependents = dependencies::active_dependents(&nodes, &[idx]);
+    let mut removed = vec![idx];
+    removed.extend(dependents.iter().copied());
+    let orphans = dependencies::orphaned_by(&nodes, &removed);
+    let disabled = dependencies::disabled_dependencies(&nodes, idx);
+    Ok(ContentDependencyReport {
+        file_name: nodes[idx].file_name.clone(),
+        dependents: dependencies::names(&nodes, &dependents),
+        orphans: dependencies::names(&nodes, &orphans),
+        disabled_dependencies: dependencies::names(&nodes, &disabled),
+    })
+}
+
+pub async fn list_orphaned_mods_impl(
+    app: &AppHandle,
+    instance_id: String,
+) -> AppResult<Vec<String>> {
+    let nodes = load_mod_graph(app, &instance_id).await?;
+    let orphans = dependencies::current_orphans(&nodes);
+    Ok(dependencies::names(&nodes, &orphans))
+}
+
+// Decide que mods se ven afectados al quitar/desactivar (`enable` = false) o activar uno.
+// Sin `cascade` se devuelve error si el cambio rompe otros mods.
+fn plan_mod_change(
+    nodes: &[dependencies::ModNode],
+    idx: usize,
+    enable: bool,
+    cascade: Option<bool>,
+    remove_orphans: bool,
+) -> AppResult<Vec<usize>> {
+    let mut targets = vec![idx];
+    let name = &nodes[idx].file_name;
+    if enable {
+        let missing = dependencies::disabled_dependencies(nodes, idx);
+        if !missing.is_empty() {
+            match cascade {
+                Some(true) => targets.extend(missing),
+                Some(false) => {}
+                None => {
+                    return Err(format!(
+                        "{} necesita mods desactivados: {}",
+                        name,
+                        dependencies::names(nodes, &missing).join(", ")
+                    )
+                    .into())
+                }
+            }
+        }
+        return Ok(targets);
+    }
+    let dependents = dependencies::active_dependents(nodes, &[idx]);
+    if !dependents.is_empty() {
+        match cascade {
+            Some(true) => targets.extend(dependents),
+            Some(false) => {}
+            None => {
+                return Err(format!(
+                    "{} es requerido por: {}",
+                    name,
+                    dependencies::names(nodes, &dependents).join(", ")
+                )
+                .into())
+            }
+        }
+    }
+    if remove_orphans {
+        let orphans = dependencies::orphaned_by(nodes, &targets);
+        targets.extend(orphans);
+    }
+    Ok(targets)
+}
+
+// Renombra `<clean>` <-> `<clean>.disabled` dentro de `dir`.
+async fn set_file_enabled(dir: &Path, clean_name: &str, enabled: bool) -> AppResult<()> {
+    let disabled = dir.join(format!("{}.disabled", clean_name));
+    let (from, to) =
+        if enabled { (disabled, dir.join(clean_name)) } else { (dir.join(clean_name), disabled) };
+    tokio_fs::rename(&from, &to).await.map_err(|e| crate::error::AppError::Message(e.to_string()))
+}
+
+/// Activa o desactiva un archivo. En mods respeta las dependencias de mods.json
+/// y devuelve los nombres de todos los archivos que cambiaron.
 pub async fn toggle_instance_content_impl(
     app: &AppHandle,
     instance_id: String,
     kind: String,
     file_name: String,
     enabled: bool,
-) -> AppResult<()> {
+    cascade: Option<bool>,
+    remove_orphans: Option<bool>,
+) -> AppResult<Vec<String>> {
     let dir = instance_kind_dir(app, &instance_id, &kind)?;
     let path = dir.join(&file_name);
     if !path.exists() {
@@ -190,56 +422,97 @@ pub async fn toggle_instance_content_impl(
     }
     let (clean_name, is_enabled) = strip_disabled(&file_name);
     if enabled == is_enabled {
-        return Ok(());
+        return Ok(Vec::new());
     }
-    let new_name = if enabled { clean_name } else { format!("{}.disabled", clean_name) };
-    tokio_fs::rename(&path, dir.join(&new_name))
-        .await
-        .map_err(|e| crate::error::AppError::Message(e.to_string()))?;
-    if kind == "mods" {
-        let _ = refresh_instance_mods_cache(app, &instance_id).await;
+    if kind != "mods" {
+        set_file_enabled(&dir, &clean_name, enabled).await?;
+        return Ok(vec![clean_name]);
     }
-    Ok(())
+
+    let nodes = load_mod_graph(app, &instance_id).await?;
+    let idx = dependencies::find_node(&nodes, &file_name)
+        .ok_or_else(|| crate::error::AppError::Message("Archivo no encontrado".to_string()))?;
+    let targets = plan_mod_change(&nodes, idx, enabled, cascade, remove_orphans.unwrap_or(false))?;
+    let mut changed = Vec::new();
+    for i in targets {
+        let node = &nodes[i];
+        if node.enabled == enabled {
+            continue;
+        }
+        set_file_enabled(&dir, &node.file_name, enabled).await?;
+        changed.push(node.file_name.clone());
+    }
+    let _ = refresh_instance_mods_cache(app, &instance_id).await;
+    if changed.len() > 1 {
+        let _ = append_action_log(
+            app,
+            &format!(
+                "content_toggle_cascade instance={} enabled={} files={}",
+                instance_id,
+                enabled,
+                changed.join(",")
+            ),
+        )
+        .await;
+    }
+    Ok(changed)
 }
 
+/// Borra un archivo. En mods respeta las dependencias de mods.json y puede arrastrar
+/// dependientes (`cascade`) y librerias huerfanas (`remove_orphans`).
 pub async fn delete_instance_content_impl(
     app: &AppHandle,
     instance_id: String,
     kind: String,
     file_name: String,
-) -> AppResult<()> {
+    cascade: Option<bool>,
+    remove_orphans: Option<bool>,
+) -> AppResult<Vec<String>> {
     let dir = instance_kind_dir(app, &instance_id, &kind)?;
     let path = dir.join(&file_name);
     if !path.exists() {
         return Err("Archivo no encontrado".to_string().into());
     }
-    tokio_fs::remove_file(path)
-        .await
-        .map_err(|e| crate::error::AppError::Message(e.to_string()))?;
 
-    if kind == "mods" || kind == "resourcepacks" || kind == "shaderpacks" {
-        let (clean_name, _) = strip_disabled(&file_name);
-        let target_kind = if kind == "mods" {
-            "mods"
-        } else if kind == "resourcepacks" {
-            "resourcepacks"
-        } else {
-            "shaderpacks"
-        };
-        let mut metadata = load_mods_metadata(app, &instance_id).await;
-        metadata.retain(|m| {
-            let matches_kind = metadata_kind_matches(&m.kind, target_kind);
-            if !matches_kind {
-                return true;
-            }
-            m.file_name != file_name && m.file_name != clean_name
-        });
-        save_mods_metadata(app, &instance_id, &metadata).await;
-        if kind == "mods" {
-            let _ = refresh_instance_mods_cache(app, &instance_id).await;
+    let mut files = vec![(file_name.clone(), strip_disabled(&file_name).0)];
+    if kind == "mods" {
+        let nodes = load_mod_graph(app, &instance_id).await?;
+        if let Some(idx) = dependencies::find_node(&nodes, &file_name) {
+            let targets =
+                plan_mod_change(&nodes, idx, false, cascade, remove_orphans.unwrap_or(false))?;
+            files = targets
+                .into_iter()
+                .map(|i| (nodes[i].disk_name(), nodes[i].file_name.clone()))
+                .collect();
         }
     }
-    Ok(())
+
+    for (disk_name, _) in files.iter() {
+        tokio_fs::remove_file(dir.join(disk_name))
+            .await
+            .map_err(|e| crate::error::AppError::Message(e.to_string()))?;
+    }
+
+    let mut metadata = load_mods_metadata(app, &instance_id).await;
+    metadata.retain(|m| {
+        if !metadata_kind_matches(&m.kind, &kind) {
+            return true;
+        }
+        !files.iter().any(|(disk_name, clean)| m.file_name == *disk_name || m.file_name == *clean)
+    });
+    save_mods_metadata(app, &instance_id, &metadata).await;
+    if kind == "mods" {
+        let _ = refresh_instance_mods_cache(app, &instance_id).await;
+    }
+    let removed: Vec<String> = files.into_iter().map(|(_, clean)| clean).collect();
+    if removed.len() > 1 {
+        let _ = append_action_log(
+            app,
+            &format!("content_delete_cascade instance={} files={}", instance_id, removed.join(",")),
+        )
+        .await;
+    }
+    Ok(removed)
 }
 
 pub fn open_instance_content_folder_impl(
@@ -317,28 +590,63 @@ pub async fn list_instance_reports_impl(
     let mut out = Vec::new();
     out.extend(list_dir_entries(&logs_dir(&base), "log").await);
     out.extend(list_dir_entries(&crashes_dir(&base), "crash").await);
-    out.sort_by(|a, b| b.modified.cmp(&a.modified));
+    out.sort_by_key(|entry| std::cmp::Reverse(entry.modified));
     Ok(out)
 }
 
-async fn read_tail(path: &Path, max_bytes: u64) -> AppResult<String> {
-    let path = path.to_path_buf();
-    let data = tokio::task::spawn_blocking(move || -> AppResult<Vec<u8>> {
-        use std::io::{Read, Seek, SeekFrom};
-        let mut file = std::fs::File::open(&path)
-            .map_err(|e| crate::error::AppError::Message(e.to_string()))?;
+/// Lector por lineas de un log o reporte; los `.gz` se descomprimen al vuelo.
+fn open_log_reader(path: &Path) -> std::io::Result<Box<dyn std::io::BufRead>> {
+    let file = std::fs::File::open(path)?;
+    if path.extension().and_then(|e| e.to_str()) == Some("gz") {
+        Ok(Box::new(std::io::BufReader::new(flate2::read::GzDecoder::new(file))))
+    } else {
+        Ok(Box::new(std::io::BufReader::new(file)))
+    }
+}
+
+/// Ultimos `max_bytes` de un log o reporte. Los `.gz` de la rotacion se descomprimen al vuelo.
+fn read_log_tail(path: &Path, max_bytes: u64) -> AppResult<String> {
+    use std::io::{Read, Seek, SeekFrom};
+    let mut file =
+        std::fs::File::open(path).map_err(|e| crate::error::AppError::Message(e.to_string()))?;
+    let is_gz = path.extension().and_then(|e| e.to_str()) == Some("gz");
+    let mut buf = Vec::new();
+    if is_gz {
+        // No se puede saltar al final de un gzip: se descomprime entero y se conserva la cola.
+        let mut decoder = flate2::read::GzDecoder::new(file);
+        let mut chunk = [0u8; 64 * 1024];
+        loop {
+            let n = decoder
+                .read(&mut chunk)
+                .map_err(|e| crate::error::AppError::Message(e.to_string()))?;
+            if n == 0 {
+                break;
+            }
+            buf.extend_from_slice(&chunk[..n]);
+            if buf.len() as u64 > max_bytes * 2 {
+                let excess = buf.len() - max_bytes as usize;
+                buf.drain(..excess);
+            }
+        }
+        if buf.len() as u64 > max_bytes {
+            let excess = buf.len() - max_bytes as usize;
+            buf.drain(..excess);
+        }
+    } else {
         let len =
             file.metadata().map_err(|e| crate::error::AppError::Message(e.to_string()))?.len();
-        let start = len.saturating_sub(max_bytes);
-        file.seek(SeekFrom::Start(start))
+        file.seek(SeekFrom::Start(len.saturating_sub(max_bytes)))
             .map_err(|e| crate::error::AppError::Message(e.to_string()))?;
-        let mut buf = Vec::new();
         file.read_to_end(&mut buf).map_err(|e| crate::error::AppError::Message(e.to_string()))?;
-        Ok(buf)
-    })
-    .await
-    .map_err(|e| crate::error::AppError::Message(e.to_string()))??;
-    String::from_utf8(data).map_err(|e| crate::error::AppError::Message(e.to_string()))
+    }
+    Ok(String::from_utf8_lossy(&buf).to_string())
+}
+
+async fn read_tail(path: &Path, max_bytes: u64) -> AppResult<String> {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || read_log_tail(&path, max_bytes))
+        .await
+        .map_err(|e| crate::error::AppError::Message(e.to_string()))?
 }
 
 pub async fn read_instance_report_impl(
@@ -374,7 +682,10 @@ pub async fn upsert_mod_metadata(
         .iter_mut()
         .find(|e| e.file_name == entry.file_name && metadata_kind_matches(&e.kind, &target_kind))
     {
+        // Fijar es una decision del usuario; reinstalar o identificar el archivo no la borra.
+        let pinned = existing.pinned || entry.pinned;
         *existing = entry;
+        existing.pinned = pinned;
     } else {
         entries.push(entry);
     }
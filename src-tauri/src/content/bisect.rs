@@ -0,0 +1,320 @@
+use crate::error::{AppError, AppResult};
+use crate::instances::refresh_instance_mods_cache;
+use crate::models::{ModBisectSession, ModSetEntry};
+use crate::utils::append_action_log;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use tauri::AppHandle;
+use tokio::fs as tokio_fs;
+
+use super::dependencies::{self, ModNode};
+use super::{instance_kind_dir, load_mod_graph, metadata_dir, set_file_enabled};
+
+fn session_path(app: &AppHandle, instance_id: &str) -> PathBuf {
+    metadata_dir(app, instance_id).join("bisect.json")
+}
+
+async fn load_session(app: &AppHandle, instance_id: &str) -> Option<ModBisectSession> {
+    let raw = tokio_fs::read_to_string(session_path(app, instance_id)).await.ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+async fn save_session(
+    app: &AppHandle,
+    instance_id: &str,
+    session: &ModBisectSession,
+) -> AppResult<()> {
+    let path = session_path(app, instance_id);
+    if let Some(parent) = path.parent() {
+        tokio_fs::create_dir_all(parent).await.map_err(|e| AppError::Message(e.to_string()))?;
+    }
+    let raw =
+        serde_json::to_string_pretty(session).map_err(|e| AppError::Message(e.to_string()))?;
+    tokio_fs::write(path, raw).await.map_err(|e| AppError::Message(e.to_string()))
+}
+
+async fn apply_enabled(
+    app: &AppHandle,
+    instance_id: &str,
+    nodes: &[ModNode],
+    wanted: &HashSet<String>,
+) -> AppResult<()> {
+    let dir = instance_kind_dir(app, instance_id, "mods")?;
+    for node in nodes {
+        let enable = wanted.contains(&node.file_name);
+        if node.enabled != enable {
+            set_file_enabled(&dir, &node.file_name, enable).await?;
+        }
+    }
+    let _ = refresh_instance_mods_cache(app, instance_id).await;
+    Ok(())
+}
+
+// Activa `testing`, los grupos retenidos, los culpables ya aislados, sus dependencias y las
+// librerias que ya estaban activas al empezar.
+async fn apply_step(
+    app: &AppHandle,
+    instance_id: &str,
+    session: &ModBisectSession,
+) -> AppResult<()> {
+    let nodes = load_mod_graph(app, instance_id).await?;
+    let originally_enabled: HashSet<&str> =
+        session.original.iter().filter(|e| e.enabled).map(|e| e.file_name.as_str()).collect();
+    let roots: Vec<usize> = nodes
+        .iter()
+        .enumerate()
+        .filter(|(i, n)| {
+            session.testing.contains(&n.file_name)
+                || session.found.contains(&n.file_name)
+                || session.held.iter().any(|group| group.contains(&n.file_name))
+                || dependencies::is_library(&nodes, *i)
+        })
+        .map(|(i, _)| i)
+        .collect();
+    let wanted: HashSet<String> = dependencies::dependency_closure(&nodes, &roots)
+        .into_iter()
+        .map(|i| nodes[i].file_name.clone())
+        .filter(|name| originally_enabled.contains(name.as_str()))
+        .collect();
+    apply_enabled(app, instance_id, &nodes, &wanted).await
+}
+
+async fn restore_original(
+    app: &AppHandle,
+    instance_id: &str,
+    session: &ModBisectSession,
+) -> AppResult<()> {
+    let nodes = load_mod_graph(app, instance_id).await?;
+    // Los mods que no existian al empezar se dejan como esten.
+    let wanted: HashSet<String> = nodes
+        .iter()
+        .filter(|n| match session.original.iter().find(|e| e.file_name == n.file_name) {
+            Some(entry) => entry.enabled,
+            None => n.enabled,
+        })
+        .map(|n| n.file_name.clone())
+        .collect();
+    apply_enabled(app, instance_id, &nodes, &wanted).await
+}
+
+fn first_half(candidates: &[String]) -> Vec<String> {
+    candidates[..candidates.len().div_ceil(2)].to_vec()
+}
+
+async fn finish(
+    app: &AppHandle,
+    instance_id: &str,
+    mut session: ModBisectSession,
+) -> AppResult<ModBisectSession> {
+    restore_original(app, instance_id, &session).await?;
+    session.finished = true;
+    session.result = session.found.clone();
+    session.testing = Vec::new();
+    save_session(app, instance_id, &session).await?;
+    let _ = append_action_log(
+        app,
+        &format!(
+            "mod_bisect_done instance={} steps={} result={}",
+            instance_id,
+            session.steps,
+            session.result.join(",")
+        ),
+    )
+    .await;
+    Ok(session)
+}
+
+/// Empieza la busqueda con todos los mods activos que no son librerias como sospechosos.
+pub async fn start_mod_bisect_impl(
+    app: &AppHandle,
+    instance_id: String,
+) -> AppResult<ModBisectSession> {
+    if let Some(existing) = load_session(app, &instance_id).await {
+        if !existing.finished {
+            return Err("Ya hay una busqueda en curso en esta instancia".to_string().into());
+        }
+    }
+    let nodes = load_mod_graph(app, &instance_id).await?;
+    let candidates: Vec<String> = nodes
+        .iter()
+        .enumerate()
+        .filter(|(i, n)| n.enabled && !dependencies::is_library(&nodes, *i))
+        .map(|(_, n)| n.file_name.clone())
+        .collect();
+    if candidates.len() < 2 {
+        return Err("Hacen falta al menos dos mods activos para buscar".to_string().into());
+    }
+    let session = ModBisectSession {
+        started_at: chrono::Utc::now().timestamp_millis(),
+        original: nodes
+            .iter()
+            .map(|n| ModSetEntry {
+                kind: "mods".to_string(),
+                file_name: n.file_name.clone(),
+                enabled: n.enabled,
+            })
+            .collect(),
+        testing: first_half(&candidates),
+        candidates,
+        second_half: false,
+        held: Vec::new(),
+        found: Vec::new(),
+        steps: 1,
+        finished: false,
+        result: Vec::new(),
+    };
+    // Se guarda antes de tocar archivos para poder restaurar aunque se cierre el launcher.
+    save_session(app, &instance_id, &session).await?;
+    apply_step(app, &instance_id, &session).await?;
+    let _ = append_action_log(
+        app,
+        &format!(
+            "mod_bisect_start instance={} candidates={}",
+            instance_id,
+            session.candidates.len()
+        ),
+    )
+    .await;
+    Ok(session)
+}
+
+pub async fn get_mod_bisect_impl(
+    app: &AppHandle,
+    instance_id: String,
+) -> AppResult<Option<ModBisectSession>> {
+    Ok(load_session(app, &instance_id).await)
+}
+
+// Con todo lo activo el juego falla (candidatos, retenidos y culpables). Si quedo un solo
+// candidato es culpable, y se sigue con el siguiente grupo retenido. Devuelve false al terminar.
+fn next_candidates(session: &mut ModBisectSession) -> bool {
+    while session.candidates.len() <= 1 {
+        session.found.append(&mut session.candidates);
+        match session.held.pop() {
+            Some(group) => session.candidates = group,
+            None => return false,
+        }
+    }
+    session.testing = first_half(&session.candidates);
+    session.second_half = false;
+    true
+}
+
+// Aplica el resultado de la prueba actual a la sesion. Devuelve false si la busqueda termino.
+fn advance(session: &mut ModBisectSession, crashed: bool) -> bool {
+    if crashed {
+        session.candidates = session.testing.clone();
+    } else if !session.second_half {
+        session.testing =
+            session.candidates.iter().filter(|c| !session.testing.contains(c)).cloned().collect();
+        session.second_half = true;
+        session.steps += 1;
+        return true;
+    } else {
+        // Ninguna mitad falla por separado: el fallo necesita mods de ambas. Se deja activa
+        // la primera mitad mientras se reduce la otra, y despues se reduce la primera.
+        session.held.push(first_half(&session.candidates));
+        session.candidates = session.testing.clone();
+    }
+    if !next_candidates(session) {
+        return false;
+    }
+    session.steps += 1;
+    true
+}
+
+/// Registra el resultado de la prueba actual (`crashed` = el juego fallo) y prepara la siguiente.
+pub async fn mark_mod_bisect_impl(
+    app: &AppHandle,
+    instance_id: String,
+    crashed: bool,
+) -> AppResult<ModBisectSession> {
+    let mut session = load_session(app, &instance_id)
+        .await
+        .ok_or_else(|| AppError::Message("No hay una busqueda en curso".to_string()))?;
+    if session.finished {
+        return Ok(session);
+    }
+    if !advance(&mut session, crashed) {
+        return finish(app, &instance_id, session).await;
+    }
+    save_session(app, &instance_id, &session).await?;
+    apply_step(app, &instance_id, &session).await?;
+    Ok(session)
+}
+
+/// Restaura el estado original de `mods/` y borra la sesion.
+pub async fn end_mod_bisect_impl(app: &AppHandle, instance_id: String) -> AppResult<()> {
+    let session = match load_session(app, &instance_id).await {
+        Some(session) => session,
+        None => return Ok(()),
+    };
+    if !session.finished {
+        restore_original(app, &instance_id, &session).await?;
+    }
+    let _ = tokio_fs::remove_file(session_path(app, &instance_id)).await;
+    let _ = append_action_log(app, &format!("mod_bisect_end instance={}", instance_id)).await;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(candidates: &[&str]) -> ModBisectSession {
+        let candidates: Vec<String> = candidates.iter().map(|c| c.to_string()).collect();
+        ModBisectSession {
+            started_at: 0,
+            original: Vec::new(),
+            testing: first_half(&candidates),
+            candidates,
+            second_half: false,
+            held: Vec::new(),
+            found: Vec::new(),
+            steps: 1,
+            finished: false,
+            result: Vec::new(),
+        }
+    }
+
+    // Simula la busqueda: el juego falla si estan activos todos los mods de `culprits`.
+    fn run(candidates: &[&str], culprits: &[&str]) -> Vec<String> {
+        let mut session = session(candidates);
+        for _ in 0..32 {
+            let active: Vec<&String> = session
+                .testing
+                .iter()
+                .chain(session.found.iter())
+                .chain(session.held.iter().flatten())
+                .collect();
+            let crashed = culprits.iter().all(|c| active.iter().any(|a| a == c));
+            if !advance(&mut session, crashed) {
+                let mut found = session.found;
+                found.sort();
+                return found;
+            }
+        }
+        panic!("la busqueda no termino");
+    }
+
+    #[test]
+    fn first_half_rounds_up() {
+        let mods: Vec<String> = ["a", "b", "c"].iter().map(|m| m.to_string()).collect();
+        assert_eq!(first_half(&mods), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn finds_single_culprit() {
+        assert_eq!(run(&["a", "b", "c", "d", "e"], &["d"]), vec!["d"]);
+    }
+
+    #[test]
+    fn finds_culprits_split_across_halves() {
+        assert_eq!(run(&["a", "b", "c", "d"], &["a", "c"]), vec!["a", "c"]);
+    }
+
+    #[test]
+    fn finds_culprits_in_same_half() {
+        assert_eq!(run(&["a", "b", "c", "d", "e", "f"], &["a", "b"]), vec!["a", "b"]);
+    }
+}
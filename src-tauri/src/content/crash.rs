@@ -0,0 +1,416 @@
+use crate::error::{AppError, AppResult};
+use crate::models::{CrashAnalysis, CrashCause, InstanceContentItem};
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+use super::{crashes_dir, instance_dir, list_instance_content_impl, logs_dir, read_log_tail};
+
+// Ids que aparecen en trazas y reportes pero no corresponden a un jar de mods/.
+const CORE_IDS: [&str; 7] =
+    ["minecraft", "forge", "neoforge", "fml", "fabricloader", "quilt_loader", "java"];
+
+// Los reportes enormes suelen repetir la misma traza; con el final alcanza.
+const MAX_REPORT_BYTES: u64 = 4 * 1024 * 1024;
+
+#[derive(Default)]
+struct ParsedReport {
+    exception: Option<String>,
+    root_cause: Option<String>,
+    suspects: Vec<String>,
+    mixin_mods: Vec<String>,
+    /// (mod que la pide, dependencia, linea original)
+    missing: Vec<(Option<String>, String, String)>,
+    out_of_memory: bool,
+    java_required: Option<u32>,
+}
+
+fn push_unique(list: &mut Vec<String>, value: String) {
+    if !value.is_empty() && !list.contains(&value) {
+        list.push(value);
+    }
+}
+
+fn is_mod_id(s: &str) -> bool {
+    !s.is_empty()
+        && s.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+        && !CORE_IDS.contains(&s)
+}
+
+// "java.lang.IllegalStateException: algo" -> Some(linea). Acepta prefijos de log y "Caused by:".
+fn exception_line(line: &str) -> Option<String> {
+    let mut text = line.trim();
+    if let Some(rest) = text.strip_prefix("Caused by: ") {
+        text = rest;
+    } else if let Some(idx) = text.find("Exception in thread ") {
+        text = text[idx..].split_once("\" ").map(|(_, rest)| rest).unwrap_or("");
+    }
+    let class = text.split([':', ' ']).next().unwrap_or("");
+    let simple = class.rsplit('.').next().unwrap_or("");
+    let looks_like_class = class.contains('.')
+        && class.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '$'))
+        && (simple.ends_with("Exception") || simple.ends_with("Error"));
+    looks_like_class.then(|| text.chars().take(300).collect())
+}
+
+// "Sodium (sodium), Version: 0.5.3" -> "sodium"
+fn id_in_parens(s: &str) -> Option<String> {
+    let start = s.find('(')?;
+    let end = start + s[start..].find(')')?;
+    let id = s[start + 1..end].trim().to_lowercase();
+    is_mod_id(&id).then_some(id)
+}
+
+fn quoted_values(s: &str) -> Vec<String> {
+    s.split('\'').skip(1).step_by(2).map(|v| v.trim().to_string()).collect()
+}
+
+fn parse_suspects(lines: &[&str], out: &mut Vec<String>) {
+    for (i, line) in lines.iter().enumerate() {
+        let trimmed = line.trim();
+        let rest = match trimmed
+            .strip_prefix("Suspected Mods:")
+            .or_else(|| trimmed.strip_prefix("Suspected Mod:"))
+        {
+            Some(rest) => rest,
+            None => continue,
+        };
+        let mut entries = vec![rest.trim()];
+        // Cada mod va en una linea con un nivel de sangria; los detalles llevan dos.
+        for next in &lines[i + 1..] {
+            if next.starts_with("\t\t") || next.starts_with("        ") {
+                continue;
+            }
+            if next.starts_with('\t') || next.starts_with("    ") {
+                entries.push(next.trim());
+            } else {
+                break;
+            }
+        }
+        for entry in entries {
+            if entry.is_empty() || entry.eq_ignore_ascii_case("none") {
+                continue;
+            }
+            let id = id_in_parens(entry).or_else(|| {
+                let first = entry.split([',', ' ']).next().unwrap_or("").to_lowercase();
+                is_mod_id(&first).then_some(first)
+            });
+            if let Some(id) = id {
+                push_unique(out, id);
+            }
+        }
+    }
+}
+
+// Forge/NeoForge marcan cada frame con el mod que lo transformo: `at TRANSFORMER/create@0.5.1/...`
+fn parse_transformer_frames(lines: &[&str], out: &mut Vec<String>) {
+    for line in lines {
+        if let Some(idx) = line.find("TRANSFORMER/") {
+            let rest = &line[idx + "TRANSFORMER/".len()..];
+            if let Some((id, _)) = rest.split_once('@') {
+                let id = id.to_lowercase();
+                if is_mod_id(&id) {
+                    push_unique(out, id);
+                }
+            }
+        }
+    }
+}
+
+fn parse_mixin_failures(lines: &[&str], out: &mut Vec<String>) {
+    for line in lines {
+        let lower = line.to_lowercase();
+        if !lower.contains("mixin")
+            || !(lower.contains("fail") || lower.contains("error") || lower.contains("exception"))
+        {
+            continue;
+        }
+        for marker in ["for mod ", "from mod "] {
+            if let Some(idx) = lower.find(marker) {
+                let id: String = lower[idx + marker.len()..]
+                    .chars()
+                    .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+                    .collect();
+                let id = id.trim_end_matches('.').to_string();
+                if is_mod_id(&id) {
+                    push_unique(out, id);
+                }
+            }
+        }
+        // Sin "from mod" queda el nombre de la config: `sodium.mixins.json` -> sodium.
+        if let Some(idx) = lower.find(".mixins.json") {
+            let mut id: Vec<char> = lower[..idx]
+                .chars()
+                .rev()
+                .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-'))
+                .collect();
+            id.reverse();
+            let id: String = id.into_iter().collect();
+            if is_mod_id(&id) {
+                push_unique(out, id);
+            }
+        }
+    }
+}
+
+fn parse_missing_dependencies(lines: &[&str], out: &mut Vec<(Option<String>, String, String)>) {
+    for line in lines {
+        let trimmed = line.trim();
+        // Forge/NeoForge: "Mod ID: 'jei', Requested by: 'foo', Expected range: ..., Actual version: '[MISSING]'"
+        if trimmed.starts_with("Mod ID:") && trimmed.contains("Requested by:") {
+            let values = quoted_values(trimmed);
+            if let Some(dep) = values.first() {
+                out.push((values.get(1).cloned(), dep.to_lowercase(), trimmed.to_string()));
+            }
+            continue;
+        }
+        // Fabric: "- Mod 'Sodium Extra' (sodium-extra) 0.4 requires any version of 'Sodium' (sodium), which is missing!"
+        if trimmed.contains(" requires ")
+            && (trimmed.contains("which is missing") || trimmed.contains("but only"))
+        {
+            let ids: Vec<String> = trimmed
+                .match_indices('(')
+                .filter_map(|(idx, _)| {
+                    id_in_parens(&trimmed[idx..]).or_else(|| {
+                        let end = trimmed[idx..].find(')')?;
+                        let inner = trimmed[idx + 1..idx + end].trim().to_lowercase();
+                        (inner == "java").then_some(inner)
+                    })
+                })
+                .collect();
+            if ids.len() >= 2 {
+                let dep = ids.last().cloned().unwrap_or_default();
+                out.push((ids.first().cloned(), dep, trimmed.trim_start_matches("- ").to_string()));
+            }
+        }
+    }
+}
+
+// "class file version 65.0" -> Java 21
+fn parse_java_requirement(text: &str) -> Option<u32> {
+    let idx = text.find("class file version ")?;
+    let digits: String = text[idx + "class file version ".len()..]
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+    let version = digits.parse::<u32>().ok()?;
+    (version > 44).then(|| version - 44)
+}
+
+fn parse_report(text: &str) -> ParsedReport {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut parsed = ParsedReport::default();
+
+    // En los crash reports la excepcion va justo despues de "Description:".
+    let start = lines.iter().position(|l| l.starts_with("Description:")).unwrap_or(0);
+    parsed.exception = lines[start..].iter().find_map(|l| exception_line(l));
+    parsed.root_cause = lines
+        .iter()
+        .rev()
+        .find(|l| l.trim().starts_with("Caused by: "))
+        .and_then(|l| exception_line(l));
+
+    parse_suspects(&lines, &mut parsed.suspects);
+    parse_transformer_frames(&lines, &mut parsed.suspects);
+    parse_mixin_failures(&lines, &mut parsed.mixin_mods);
+    parse_missing_dependencies(&lines, &mut parsed.missing);
+
+    parsed.out_of_memory = text.contains("java.lang.OutOfMemoryError")
+        || text.contains("GC overhead limit exceeded")
+        || text.contains("Out of memory.");
+    if text.contains("UnsupportedClassVersionError") {
+        parsed.java_required = parse_java_requirement(text);
+    }
+    parsed
+}
+
+fn normalize(s: &str) -> String {
+    s.to_lowercase().chars().filter(|c| c.is_ascii_alphanumeric()).collect()
+}
+
+// Busca los archivos de mods/ que declaran ese id (o nombre); si ningun jar lo declara,
+// prueba con el nombre del archivo.
+fn files_for(mods: &[InstanceContentItem], id: &str) -> Vec<String> {
+    let by_info: Vec<String> = mods
+        .iter()
+        .filter(|m| {
+            m.mod_info.as_ref().is_some_and(|info| {
+                info.mod_id.eq_ignore_ascii_case(id)
+                    || info.name.as_deref().is_some_and(|n| n.eq_ignore_ascii_case(id))
+            })
+        })
+        .map(|m| m.file_name.clone())
+        .collect();
+    if !by_info.is_empty() {
+        return by_info;
+    }
+    let key = normalize(id);
+    if key.len() < 3 {
+        return Vec::new();
+    }
+    mods.iter()
+        .filter(|m| normalize(&m.name).starts_with(&key))
+        .map(|m| m.file_name.clone())
+        .collect()
+}
+
+fn build_causes(parsed: &ParsedReport, mods: &[InstanceContentItem]) -> Vec<CrashCause> {
+    let mut causes = Vec::new();
+    let mut blamed: Vec<String> = Vec::new();
+
+    for (requester, dep, line) in &parsed.missing {
+        if dep == "java" {
+            causes.push(CrashCause {
+                kind: "java_version".to_string(),
+                summary: line.clone(),
+                files: requester.as_deref().map(|r| files_for(mods, r)).unwrap_or_default(),
+                hint: "Elige otra version de Java en los ajustes de la instancia o usa la descarga automatica.".to_string(),
+            });
+            continue;
+        }
+        let who = requester.clone().unwrap_or_else(|| "Un mod".to_string());
+        let installed = !files_for(mods, dep).is_empty();
+        causes.push(CrashCause {
+            kind: "missing_dependency".to_string(),
+            summary: if installed {
+                format!("{} necesita otra version de {}", who, dep)
+            } else {
+                format!("{} necesita {}, que no esta instalado", who, dep)
+            },
+            files: requester.as_deref().map(|r| files_for(mods, r)).unwrap_or_default(),
+            hint: if installed {
+                format!("Actualiza {} o {} a versiones compatibles entre si.", dep, who)
+            } else {
+                format!("Instala {} desde la pestana de mods o quita {}.", dep, who)
+            },
+        });
+        if let Some(r) = requester {
+            push_unique(&mut blamed, r.to_lowercase());
+        }
+    }
+
+    if let Some(java) = parsed.java_required {
+        causes.push(CrashCause {
+            kind: "java_version".to_string(),
+            summary: format!("El juego o un mod necesita Java {} o superior", java),
+            files: Vec::new(),
+            hint: format!(
+                "Selecciona Java {} o superior en los ajustes de la instancia, o usa la descarga automatica.",
+                java
+            ),
+        });
+    }
+
+    if parsed.out_of_memory {
+        causes.push(CrashCause {
+            kind: "out_of_memory".to_string(),
+            summary: "El juego se quedo sin memoria".to_string(),
+            files: Vec::new(),
+            hint: "Sube la memoria maxima en los ajustes de la instancia (4-6 GB suele bastar con muchos mods) y cierra otros programas.".to_string(),
+        });
+    }
+
+    for id in &parsed.mixin_mods {
+        causes.push(CrashCause {
+            kind: "mixin".to_string(),
+            summary: format!("Fallo al aplicar los mixins de {}", id),
+            files: files_for(mods, id),
+            hint: format!(
+                "Comprueba que {} sea para esta version de Minecraft y este loader; actualizalo o desactivalo.",
+                id
+            ),
+        });
+        push_unique(&mut blamed, id.clone());
+    }
+
+    for id in parsed.suspects.iter().filter(|id| !blamed.contains(id)) {
+        causes.push(CrashCause {
+            kind: "suspected_mod".to_string(),
+            summary: format!("El reporte apunta a {}", id),
+            files: files_for(mods, id),
+            hint: format!("Desactiva {} y vuelve a probar; si se arregla, busca otra version.", id),
+        });
+    }
+
+    if causes.is_empty() {
+        if let Some(exception) = parsed.root_cause.as_ref().or(parsed.exception.as_ref()) {
+            causes.push(CrashCause {
+                kind: "exception".to_string(),
+                summary: exception.clone(),
+                files: Vec::new(),
+                hint: "No hay un mod senalado. Si el fallo empezo al agregar mods, usa la busqueda por mitades para encontrarlo.".to_string(),
+            });
+        }
+    }
+    causes
+}
+
+async fn newest_report(dir: &Path) -> Option<PathBuf> {
+    let mut newest: Option<(PathBuf, std::time::SystemTime)> = None;
+    let mut rd = tokio::fs::read_dir(dir).await.ok()?;
+    while let Ok(Some(entry)) = rd.next_entry().await {
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+        let modified = match entry.metadata().await.and_then(|m| m.modified()) {
+            Ok(modified) => modified,
+            Err(_) => continue,
+        };
+        if newest.as_ref().map(|(_, ts)| modified > *ts).unwrap_or(true) {
+            newest = Some((path, modified));
+        }
+    }
+    newest.map(|(path, _)| path)
+}
+
+/// Analiza un crash report o log. Sin `name` usa el crash report mas reciente y, si no hay,
+/// `logs/latest.log`.
+pub async fn analyze_instance_report_impl(
+    app: &AppHandle,
+    instance_id: String,
+    kind: Option<String>,
+    name: Option<String>,
+) -> AppResult<CrashAnalysis> {
+    let base = instance_dir(app, &instance_id);
+    if !base.exists() {
+        return Err("La instancia no existe".to_string().into());
+    }
+    let (kind, path) = match (kind.as_deref(), name) {
+        (Some(kind), Some(name)) => {
+            if name.contains('/') || name.contains('\\') || name.contains("..") {
+                return Err("Nombre de archivo invalido".to_string().into());
+            }
+            let folder = match kind {
+                "log" => logs_dir(&base),
+                "crash" => crashes_dir(&base),
+                _ => return Err("Tipo inválido".to_string().into()),
+            };
+            (kind.to_string(), folder.join(name))
+        }
+        _ => match newest_report(&crashes_dir(&base)).await {
+            Some(path) => ("crash".to_string(), path),
+            None => ("log".to_string(), logs_dir(&base).join("latest.log")),
+        },
+    };
+    if !path.is_file() {
+        return Err("No hay reportes para analizar".to_string().into());
+    }
+
+    let read_path = path.clone();
+    let parsed = tokio::task::spawn_blocking(move || -> AppResult<ParsedReport> {
+        Ok(parse_report(&read_log_tail(&read_path, MAX_REPORT_BYTES)?))
+    })
+    .await
+    .map_err(|e| AppError::Message(e.to_string()))??;
+    let mods =
+        list_instance_content_impl(app, instance_id, "mods".to_string()).await.unwrap_or_default();
+
+    Ok(CrashAnalysis {
+        kind,
+        name: path.file_name().and_then(|s| s.to_str()).unwrap_or_default().to_string(),
+        causes: build_causes(&parsed, &mods),
+        exception: parsed.exception,
+        root_cause: parsed.root_cause,
+        suspected_mods: parsed.suspects,
+    })
+}
@@ -0,0 +1,179 @@
+use crate::models::ModMetadataEntry;
+use std::collections::HashSet;
+use std::path::Path;
+
+use super::{metadata_kind_matches, strip_disabled};
+
+/// Un mod presente en disco junto con lo que mods.json sabe de el.
+pub(super) struct ModNode {
+    pub file_name: String,
+    pub enabled: bool,
+    version_id: Option<String>,
+    project_id: Option<String>,
+    dependencies: Vec<String>,
+}
+
+impl ModNode {
+    pub(super) fn disk_name(&self) -> String {
+        if self.enabled {
+            self.file_name.clone()
+        } else {
+            format!("{}.disabled", self.file_name)
+        }
+    }
+}
+
+// Las dependencias se guardan como version_id o, si Modrinth no fija version, como project_id.
+fn requires(node: &ModNode, target: &ModNode) -> bool {
+    node.dependencies.iter().any(|d| {
+        target.version_id.as_deref() == Some(d.as_str())
+            || target.project_id.as_deref() == Some(d.as_str())
+    })
+}
+
+pub(super) fn mod_graph(mods_dir: &Path, metadata: &[ModMetadataEntry]) -> Vec<ModNode> {
+    let entries = match std::fs::read_dir(mods_dir) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+    let mut nodes = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().to_string();
+        let (clean, enabled) = strip_disabled(&name);
+        if !clean.to_lowercase().ends_with(".jar") {
+            continue;
+        }
+        let meta = metadata
+            .iter()
+            .find(|m| m.file_name == clean && metadata_kind_matches(&m.kind, "mods"));
+        nodes.push(ModNode {
+            file_name: clean,
+            enabled,
+            version_id: meta.and_then(|m| m.version_id.clone()),
+            project_id: meta.and_then(|m| m.project_id.clone()),
+            dependencies: meta.map(|m| m.dependencies.clone()).unwrap_or_default(),
+        });
+    }
+    nodes.sort_by_key(|n| n.file_name.to_lowercase());
+    nodes
+}
+
+pub(super) fn find_node(nodes: &[ModNode], file_name: &str) -> Option<usize> {
+    let (clean, _) = strip_disabled(file_name);
+    nodes.iter().position(|n| n.file_name == clean)
+}
+
+/// Mods activos que dependen, directa o indirectamente, de alguno de `roots`.
+pub(super) fn active_dependents(nodes: &[ModNode], roots: &[usize]) -> Vec<usize> {
+    let mut seen: HashSet<usize> = roots.iter().copied().collect();
+    let mut queue = roots.to_vec();
+    let mut out = Vec::new();
+    while let Some(current) = queue.pop() {
+        for (i, node) in nodes.iter().enumerate() {
+            if node.enabled && !seen.contains(&i) && requires(node, &nodes[current]) {
+                seen.insert(i);
+                out.push(i);
+                queue.push(i);
+            }
+        }
+    }
+    out
+}
+
+/// Dependencias desactivadas que necesita `root`, siguiendo toda la cadena.
+pub(super) fn disabled_dependencies(nodes: &[ModNode], root: usize) -> Vec<usize> {
+    let mut seen: HashSet<usize> = HashSet::from([root]);
+    let mut queue = vec![root];
+    let mut out = Vec::new();
+    while let Some(current) = queue.pop() {
+        for (i, node) in nodes.iter().enumerate() {
+            if !seen.contains(&i) && requires(&nodes[current], node) {
+                seen.insert(i);
+                if !node.enabled {
+                    out.push(i);
+                }
+                queue.push(i);
+            }
+        }
+    }
+    out
+}
+
+fn has_active_dependent(nodes: &[ModNode], target: usize, ignored: &HashSet<usize>) -> bool {
+    nodes.iter().enumerate().any(|(k, n)| {
+        k != target && !ignored.contains(&k) && n.enabled && requires(n, &nodes[target])
+    })
+}
+
+/// Librerias activas que se quedan sin ningun dependiente activo al quitar `removed`.
+pub(super) fn orphaned_by(nodes: &[ModNode], removed: &[usize]) -> Vec<usize> {
+    let mut gone: HashSet<usize> = removed.iter().copied().collect();
+    let mut out = Vec::new();
+    // Se repite hasta estabilizar: una libreria huerfana puede dejar huerfanas a las suyas.
+    while let Some(j) = (0..nodes.len()).find(|&j| {
+        !gone.contains(&j)
+            && nodes[j].enabled
+            && gone.iter().any(|&r| requires(&nodes[r], &nodes[j]))
+            && !has_active_dependent(nodes, j, &gone)
+    }) {
+        gone.insert(j);
+        out.push(j);
+    }
+    out
+}
+
+/// Librerias activas de las que solo dependen mods desactivados.
+pub(super) fn current_orphans(nodes: &[ModNode]) -> Vec<usize> {
+    let none = HashSet::new();
+    (0..nodes.len())
+        .filter(|&j| {
+            nodes[j].enabled
+                && nodes.iter().enumerate().any(|(k, n)| k != j && requires(n, &nodes[j]))
+                && !has_active_dependent(nodes, j, &none)
+        })
+        .collect()
+}
+
+pub(super) fn names(nodes: &[ModNode], indices: &[usize]) -> Vec<String> {
+    indices.iter().map(|&i| nodes[i].file_name.clone()).collect()
+}
+
+/// Pares (mod activo, dependencia desactivada) que dejarian al mod sin arrancar.
+pub(super) fn broken_requirements(nodes: &[ModNode]) -> Vec<(usize, usize)> {
+    let mut out = Vec::new();
+    for (i, node) in nodes.iter().enumerate() {
+        if !node.enabled {
+            continue;
+        }
+        for (j, dep) in nodes.iter().enumerate() {
+            if i != j && !dep.enabled && requires(node, dep) {
+                out.push((i, j));
+            }
+        }
+    }
+    out
+}
+
+/// True si algun otro mod declara `target` como dependencia (es una libreria).
+pub(super) fn is_library(nodes: &[ModNode], target: usize) -> bool {
+    nodes.iter().enumerate().any(|(k, n)| k != target && requires(n, &nodes[target]))
+}
+
+/// `roots` mas todas sus dependencias, directas e indirectas, sin mirar si estan activas.
+pub(super) fn dependency_closure(nodes: &[ModNode], roots: &[usize]) -> HashSet<usize> {
+    let mut seen: HashSet<usize> = roots.iter().copied().collect();
+    let mut queue = roots.to_vec();
+    while let Some(current) = queue.pop() {
+        for (i, node) in nodes.iter().enumerate() {
+            if !seen.contains(&i) && requires(&nodes[current], node) {
+                seen.insert(i);
+                queue.push(i);
+            }
+        }
+    }
+    seen
+}
@@ -0,0 +1,185 @@
+use crate::error::{AppError, AppResult};
+use crate::instances::refresh_instance_mods_cache;
+use crate::models::{DuplicateModFile, DuplicateModGroup, DuplicateResolveResult};
+use crate::utils::append_action_log;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::path::Path;
+use tauri::AppHandle;
+use tokio::fs as tokio_fs;
+
+use super::mod_info::{self, read_mod_info, CachedModInfo};
+use super::{
+    instance_dir, instance_kind_dir, load_mods_metadata, metadata_dir, metadata_kind_matches,
+    path_modified_millis, save_mods_metadata,
+};
+
+// "1.2.10+mc1.20.1" > "1.2.9"; lo que va tras '+' es metadata de build y no cuenta.
+fn compare_mod_versions(a: &str, b: &str) -> Ordering {
+    let parse = |s: &str| -> Vec<u64> {
+        s.split('+')
+            .next()
+            .unwrap_or("")
+            .split(['.', '-', '_'])
+            .map(|seg| {
+                let digits: String = seg.chars().take_while(|c| c.is_ascii_digit()).collect();
+                digits.parse::<u64>().unwrap_or(0)
+            })
+            .collect()
+    };
+    let av = parse(a);
+    let bv = parse(b);
+    for i in 0..av.len().max(bv.len()) {
+        let a_i = *av.get(i).unwrap_or(&0);
+        let b_i = *bv.get(i).unwrap_or(&0);
+        if a_i != b_i {
+            return a_i.cmp(&b_i);
+        }
+    }
+    Ordering::Equal
+}
+
+fn newer(a: &DuplicateModFile, b: &DuplicateModFile) -> Ordering {
+    let by_version = match (&a.version, &b.version) {
+        (Some(av), Some(bv)) => compare_mod_versions(av, bv),
+        _ => Ordering::Equal,
+    };
+    by_version.then(a.modified.cmp(&b.modified))
+}
+
+/// Agrupa los jars activos de `<game_dir>/mods` por mod id y devuelve los que aparecen mas de
+/// una vez. Lee los metadatos a traves de `.launcher/mod_info.cache.json`: solo se abren los jars
+/// nuevos o modificados.
+pub(crate) fn find_duplicate_mods(game_dir: &Path) -> Vec<DuplicateModGroup> {
+    let entries = match std::fs::read_dir(game_dir.join("mods")) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+    let cache_path = game_dir.join(".launcher").join(mod_info::CACHE_FILE);
+    let mut cache = mod_info::load_cache(&cache_path);
+    let mut cache_changed = false;
+    let mut by_id: BTreeMap<String, (Option<String>, Vec<DuplicateModFile>)> = BTreeMap::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        if !path.is_file() || !file_name.to_lowercase().ends_with(".jar") {
+            continue;
+        }
+        let (size, modified) = match entry.metadata() {
+            Ok(meta) => (meta.len(), path_modified_millis(&meta)),
+            Err(_) => continue,
+        };
+        let cached = cache.get(&file_name).filter(|c| c.is_fresh(size, modified));
+        let info = match cached {
+            Some(cached) => cached.info.clone(),
+            None => {
+                let info = read_mod_info(&path);
+                cache.insert(file_name.clone(), CachedModInfo::new(size, modified, info.clone()));
+                cache_changed = true;
+                info
+            }
+        };
+        let info = match info {
+            Some(info) => info,
+            None => continue,
+        };
+        let group =
+            by_id.entry(info.mod_id.to_lowercase()).or_insert((info.name.clone(), Vec::new()));
+        group.1.push(DuplicateModFile { file_name, version: info.version, modified });
+    }
+    if cache_changed {
+        mod_info::save_cache(&cache_path, &cache);
+    }
+    by_id
+        .into_iter()
+        .filter(|(_, (_, files))| files.len() > 1)
+        .map(|(mod_id, (name, mut files))| {
+            files.sort_by(|a, b| newer(b, a));
+            let keep = files[0].file_name.clone();
+            DuplicateModGroup { mod_id, name, files, keep }
+        })
+        .collect()
+}
+
+pub(crate) fn describe_duplicates(groups: &[DuplicateModGroup]) -> String {
+    groups
+        .iter()
+        .map(|g| {
+            let files: Vec<&str> = g.files.iter().map(|f| f.file_name.as_str()).collect();
+            format!("{} ({})", g.mod_id, files.join(", "))
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+pub async fn list_duplicate_mods_impl(
+    app: &AppHandle,
+    instance_id: String,
+) -> AppResult<Vec<DuplicateModGroup>> {
+    let base = instance_dir(app, &instance_id);
+    tokio::task::spawn_blocking(move || find_duplicate_mods(&base))
+        .await
+        .map_err(|e| AppError::Message(e.to_string()))
+}
+
+/// Deja solo la version mas nueva de cada mod duplicado. Los demas jars se mueven a
+/// `.launcher/replaced/<fecha>/mods/`. Un grupo en el que habria que quitar un jar fijado no
+/// se toca y se informa en `unresolved`.
+pub async fn resolve_duplicate_mods_impl(
+    app: &AppHandle,
+    instance_id: String,
+) -> AppResult<DuplicateResolveResult> {
+    let dir = instance_kind_dir(app, &instance_id, "mods")?;
+    let groups = list_duplicate_mods_impl(app, instance_id.clone()).await?;
+    let mut result = DuplicateResolveResult { removed: Vec::new(), unresolved: Vec::new() };
+    if groups.is_empty() {
+        return Ok(result);
+    }
+    let mut metadata = load_mods_metadata(app, &instance_id).await;
+    let pinned: Vec<&str> = metadata
+        .iter()
+        .filter(|m| m.pinned && metadata_kind_matches(&m.kind, "mods"))
+        .map(|m| m.file_name.as_str())
+        .collect();
+    let ts = chrono::Utc::now().format("%Y%m%d_%H%M%S").to_string();
+    let backup_dir = metadata_dir(app, &instance_id).join("replaced").join(ts).join("mods");
+
+    let mut removed = Vec::new();
+    for group in groups.iter() {
+        let extra: Vec<&DuplicateModFile> =
+            group.files.iter().filter(|f| f.file_name != group.keep).collect();
+        if extra.iter().any(|f| pinned.contains(&f.file_name.as_str())) {
+            result.unresolved.push(group.mod_id.clone());
+            continue;
+        }
+        tokio_fs::create_dir_all(&backup_dir)
+            .await
+            .map_err(|e| AppError::Message(e.to_string()))?;
+        for file in extra {
+            tokio_fs::rename(dir.join(&file.file_name), backup_dir.join(&file.file_name))
+                .await
+                .map_err(|e| AppError::Message(e.to_string()))?;
+            removed.push(file.file_name.clone());
+        }
+    }
+
+    if !removed.is_empty() {
+        metadata.retain(|m| {
+            !(metadata_kind_matches(&m.kind, "mods") && removed.contains(&m.file_name))
+        });
+        save_mods_metadata(app, &instance_id, &metadata).await;
+        let _ = refresh_instance_mods_cache(app, &instance_id).await;
+    }
+    let _ = append_action_log(
+        app,
+        &format!(
+            "mods_dedupe instance={} removed={} unresolved={}",
+            instance_id,
+            removed.join(","),
+            result.unresolved.join(",")
+        ),
+    )
+    .await;
+    result.removed = removed;
+    Ok(result)
+}
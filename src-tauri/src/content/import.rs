@@ -0,0 +1,191 @@
+use crate::error::{AppError, AppResult};
+use crate::instances::{get_instance_impl, refresh_instance_mods_cache};
+use crate::launcher::jar_loader_markers;
+use crate::models::{ContentImportRequest, ContentImportResult, ModMetadataEntry};
+use crate::utils::append_action_log;
+use base64::Engine;
+use sha1::{Digest, Sha1};
+use std::fs;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+use super::{instance_kind_dir, metadata_dir, strip_disabled, upsert_mod_metadata};
+
+fn expected_extension(kind: &str) -> &'static str {
+    if kind == "mods" {
+        "jar"
+    } else {
+        "zip"
+    }
+}
+
+// Comprueba que el zip tenga la forma esperada para el tipo de contenido.
+fn validate_archive(kind: &str, data: &[u8]) -> AppResult<()> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(data))
+        .map_err(|_| AppError::Message("El archivo no es un zip/jar valido".to_string()))?;
+    let names: Vec<String> = (0..archive.len())
+        .filter_map(|i| archive.by_index(i).ok().map(|f| f.name().to_string()))
+        .collect();
+    let ok = match kind {
+        "resourcepacks" => names.iter().any(|n| n == "pack.mcmeta"),
+        "shaderpacks" => names.iter().any(|n| n.starts_with("shaders/")),
+        _ => names.iter().any(|n| n.ends_with(".class") || n == "META-INF/MANIFEST.MF"),
+    };
+    if !ok {
+        return Err(match kind {
+            "resourcepacks" => "El zip no contiene pack.mcmeta; no es un resource pack",
+            "shaderpacks" => "El zip no contiene la carpeta shaders/; no es un shaderpack",
+            _ => "El jar no contiene clases; no parece un mod",
+        }
+        .to_string()
+        .into());
+    }
+    Ok(())
+}
+
+fn sha1_hex(data: &[u8]) -> String {
+    format!("{:x}", Sha1::digest(data))
+}
+
+// Busca un archivo con el mismo contenido (activo o desactivado) en la carpeta destino.
+fn find_duplicate(dir: &Path, kind: &str, data: &[u8]) -> Option<String> {
+    let hash = sha1_hex(data);
+    let ext = format!(".{}", expected_extension(kind));
+    fs::read_dir(dir).ok()?.flatten().find_map(|entry| {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let (clean, _) = strip_disabled(&name);
+        if !clean.to_lowercase().ends_with(&ext) {
+            return None;
+        }
+        let meta = entry.metadata().ok()?;
+        if !meta.is_file() || meta.len() != data.len() as u64 {
+            return None;
+        }
+        let existing = fs::read(entry.path()).ok()?;
+        (sha1_hex(&existing) == hash).then_some(clean)
+    })
+}
+
+// `name.jar` -> `name-1.jar`, ... evitando chocar tambien con `.disabled`.
+fn unique_target(dir: &Path, safe_name: &str) -> PathBuf {
+    let taken =
+        |name: &str| dir.join(name).exists() || dir.join(format!("{}.disabled", name)).exists();
+    if !taken(safe_name) {
+        return dir.join(safe_name);
+    }
+    let path = Path::new(safe_name);
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("content");
+    let ext = path.extension().and_then(|s| s.to_str()).unwrap_or("");
+    for i in 1..=999 {
+        let candidate = format!("{}-{}.{}", stem, i, ext);
+        if !taken(&candidate) {
+            return dir.join(candidate);
+        }
+    }
+    dir.join(format!("{}-{}.{}", stem, chrono::Utc::now().timestamp_millis(), ext))
+}
+
+async fn read_source(request: &ContentImportRequest) -> AppResult<(String, Vec<u8>)> {
+    if let Some(path) = request.path.as_deref().filter(|p| !p.trim().is_empty()) {
+        let path = PathBuf::from(path);
+        let name = path.file_name().and_then(|s| s.to_str()).unwrap_or("").to_string();
+        let data = tokio::fs::read(&path).await.map_err(|e| AppError::Message(e.to_string()))?;
+        return Ok((name, data));
+    }
+    let (name, encoded) = match (&request.file_name, &request.data_base64) {
+        (Some(name), Some(data)) => (name, data),
+        _ => return Err("Indica una ruta o el archivo en base64".to_string().into()),
+    };
+    let name = Path::new(name).file_name().and_then(|s| s.to_str()).unwrap_or("").to_string();
+    let data = base64::engine::general_purpose::STANDARD
+        .decode(encoded.as_bytes())
+        .map_err(|_| "Archivo invalido".to_string())?;
+    Ok((name, data))
+}
+
+/// Copia un jar/zip local a la instancia validando tipo, loader y duplicados.
+pub async fn import_instance_content_impl(
+    app: &AppHandle,
+    instance_id: String,
+    request: ContentImportRequest,
+) -> AppResult<ContentImportResult> {
+    let kind = request.kind.clone();
+    let dir = instance_kind_dir(app, &instance_id, &kind)?;
+    let instance = get_instance_impl(app, &instance_id).await?;
+    let (safe_name, data) = read_source(&request).await?;
+    if safe_name.is_empty() {
+        return Err("Nombre de archivo invalido".to_string().into());
+    }
+    let ext = expected_extension(&kind);
+    if !safe_name.to_lowercase().ends_with(&format!(".{}", ext)) {
+        return Err(format!("Solo se permiten archivos .{} en {}", ext, kind).into());
+    }
+    if kind == "mods" && !matches!(instance.loader.as_str(), "fabric" | "forge" | "neoforge") {
+        return Err("La instancia no usa un loader de mods".to_string().into());
+    }
+
+    let staging_dir = metadata_dir(app, &instance_id).join("import");
+    let loader = instance.loader.clone();
+    let check_kind = kind.clone();
+    let check_dir = dir.clone();
+    let outcome = tokio::task::spawn_blocking(move || -> AppResult<Result<PathBuf, String>> {
+        validate_archive(&check_kind, &data)?;
+        fs::create_dir_all(&check_dir).map_err(|e| AppError::Message(e.to_string()))?;
+        if let Some(existing) = find_duplicate(&check_dir, &check_kind, &data) {
+            return Ok(Err(existing));
+        }
+        fs::create_dir_all(&staging_dir).map_err(|e| AppError::Message(e.to_string()))?;
+        let staged = staging_dir.join(&safe_name);
+        fs::write(&staged, &data).map_err(|e| AppError::Message(e.to_string()))?;
+        if check_kind == "mods" {
+            let markers = jar_loader_markers(&staged).unwrap_or_default();
+            if markers.excludes_loader(&loader) {
+                let _ = fs::remove_file(&staged);
+                return Err(format!(
+                    "{} no es compatible con {} (es un mod para {})",
+                    safe_name,
+                    loader,
+                    markers.describe()
+                )
+                .into());
+            }
+        }
+        let target = unique_target(&check_dir, &safe_name);
+        fs::rename(&staged, &target).map_err(|e| AppError::Message(e.to_string()))?;
+        Ok(Ok(target))
+    })
+    .await
+    .map_err(|e| AppError::Message(e.to_string()))??;
+
+    let target = match outcome {
+        Ok(target) => target,
+        Err(existing) => {
+            return Ok(ContentImportResult { file_name: existing, kind, duplicate: true });
+        }
+    };
+    let file_name = target.file_name().and_then(|s| s.to_str()).unwrap_or_default().to_string();
+    upsert_mod_metadata(
+        app,
+        &instance_id,
+        ModMetadataEntry {
+            file_name: file_name.clone(),
+            version_id: None,
+            project_id: None,
+            dependencies: Vec::new(),
+            source: Some("local".to_string()),
+            kind: Some(kind.clone()),
+            pinned: false,
+        },
+    )
+    .await?;
+    if kind == "mods" {
+        let _ = refresh_instance_mods_cache(app, &instance_id).await;
+    }
+    let _ = append_action_log(
+        app,
+        &format!("content_import instance={} kind={} file={}", instance_id, kind, file_name),
+    )
+    .await;
+    Ok(ContentImportResult { file_name, kind, duplicate: false })
+}
@@ -0,0 +1,320 @@
+use crate::error::{AppError, AppResult};
+use crate::launcher::{text_line_level, text_line_time, xml_line_timestamp};
+use crate::models::{
+    InstanceLogEntry, LogSearchCursor, LogSearchMatch, LogSearchPage, LogSearchQuery,
+};
+use regex::{Regex, RegexBuilder};
+use std::collections::VecDeque;
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+use super::{crashes_dir, instance_dir, list_dir_entries, logs_dir, open_log_reader};
+
+const DEFAULT_PAGE_SIZE: usize = 200;
+const MAX_PAGE_SIZE: usize = 1000;
+const DEFAULT_CONTEXT: usize = 2;
+const MAX_CONTEXT: usize = 10;
+// Lineas revisadas por pagina: con logs enormes la pagina vuelve a tiempo aunque haya pocos
+// resultados, y el cursor sigue desde ahi.
+const MAX_LINES_PER_PAGE: usize = 200_000;
+const MAX_LINE_CHARS: usize = 1000;
+
+struct Search {
+    matcher: Regex,
+    min_level: Option<u8>,
+    context: usize,
+    since: Option<i64>,
+    until: Option<i64>,
+}
+
+impl Search {
+    // Las lineas sin hora conocida no se descartan: no hay con que compararlas.
+    fn in_range(&self, millis: Option<i64>) -> bool {
+        match millis {
+            Some(millis) => {
+                self.since.map(|since| millis >= since).unwrap_or(true)
+                    && self.until.map(|until| millis <= until).unwrap_or(true)
+            }
+            None => true,
+        }
+    }
+}
+
+fn level_rank(level: &str) -> u8 {
+    match level.to_ascii_uppercase().as_str() {
+        "FATAL" => 5,
+        "ERROR" => 4,
+        "WARN" => 3,
+        "INFO" => 2,
+        "DEBUG" => 1,
+        _ => 0,
+    }
+}
+
+fn build_matcher(query: &LogSearchQuery) -> AppResult<Regex> {
+    if query.query.is_empty() {
+        return Err("La busqueda esta vacia".to_string().into());
+    }
+    let pattern = if query.regex { query.query.clone() } else { regex::escape(&query.query) };
+    RegexBuilder::new(&pattern)
+        .case_insensitive(!query.case_sensitive)
+        .build()
+        .map_err(|e| AppError::Message(format!("Expresion regular invalida: {}", e)))
+}
+
+// Inicio de un archivo segun su nombre: `launch-2024-01-05_12-00-00.log`,
+// `crash-2024-01-05_12.00.00-client.txt` o los rotados de vanilla `2024-01-05-1.log.gz`.
+fn file_start_millis(name: &str) -> Option<i64> {
+    let rest = name.strip_prefix("launch-").or_else(|| name.strip_prefix("crash-")).unwrap_or(name);
+    let head: String = rest.chars().take(19).collect();
+    let digits: String = head.chars().filter(|c| c.is_ascii_digit()).collect();
+    let naive = if digits.len() >= 14 {
+        chrono::NaiveDateTime::parse_from_str(&digits[..14], "%Y%m%d%H%M%S").ok()?
+    } else {
+        chrono::NaiveDate::parse_from_str(head.get(..10)?, "%Y-%m-%d").ok()?.and_hms_opt(0, 0, 0)?
+    };
+    naive.and_local_timezone(chrono::Local).earliest().map(|t| t.timestamp_millis())
+}
+
+// Un archivo cubre desde su inicio (si el nombre lo dice) hasta su ultima modificacion.
+fn in_window(entry: &InstanceLogEntry, query: &LogSearchQuery) -> bool {
+    if query.since.map(|since| entry.modified < since).unwrap_or(false) {
+        return false;
+    }
+    match (query.until, file_start_millis(&entry.name)) {
+        (Some(until), Some(start)) => start <= until,
+        _ => true,
+    }
+}
+
+/// Convierte la hora de cada linea (`[HH:MM:SS]`) en millis. Con la fecha del nombre del
+/// archivo avanza un dia cada vez que la hora vuelve atras; sin ella (`latest.log`) se toma el
+/// dia de la ultima modificacion, o el anterior si la hora quedaria despues de esa modificacion.
+struct LineClock {
+    start: Option<chrono::NaiveDateTime>,
+    modified: chrono::DateTime<chrono::Local>,
+    day_offset: i64,
+    last: Option<chrono::NaiveTime>,
+}
+
+impl LineClock {
+    fn new(entry: &InstanceLogEntry) -> Self {
+        let start = file_start_millis(&entry.name)
+            .and_then(chrono::DateTime::from_timestamp_millis)
+            .map(|t| t.with_timezone(&chrono::Local).naive_local());
+        let modified = chrono::DateTime::from_timestamp_millis(entry.modified)
+            .map(|t| t.with_timezone(&chrono::Local))
+            .unwrap_or_else(chrono::Local::now);
+        Self { start, modified, day_offset: 0, last: None }
+    }
+
+    fn start_millis(&self) -> Option<i64> {
+        self.start.and_then(local_millis)
+    }
+
+    fn at(&mut self, time: chrono::NaiveTime) -> Option<i64> {
+        let start = match self.start {
+            Some(start) => start,
+            None => {
+                let end = self.modified.naive_local();
+                let mut at = end.date().and_time(time);
+                if at > end + chrono::Duration::minutes(1) {
+                    at -= chrono::Duration::days(1);
+                }
+                return local_millis(at);
+            }
+        };
+        let previous = self.last.unwrap_or_else(|| start.time());
+        if time < previous && previous - time > chrono::Duration::hours(1) {
+            self.day_offset += 1;
+        }
+        self.last = Some(time);
+        local_millis(start.date().and_time(time) + chrono::Duration::days(self.day_offset))
+    }
+}
+
+fn local_millis(at: chrono::NaiveDateTime) -> Option<i64> {
+    at.and_local_timezone(chrono::Local).earliest().map(|t| t.timestamp_millis())
+}
+
+fn clip_line(line: &str) -> String {
+    if line.len() <= MAX_LINE_CHARS {
+        return line.to_string();
+    }
+    let mut clipped: String = line.chars().take(MAX_LINE_CHARS).collect();
+    clipped.push_str("...");
+    clipped
+}
+
+/// Recorre un archivo desde la linea `skip` y agrega hasta `want` resultados. Devuelve la
+/// linea desde la que seguir si corta antes del final.
+fn scan_file(
+    path: &Path,
+    entry: &InstanceLogEntry,
+    skip: usize,
+    search: &Search,
+    want: usize,
+    budget: &mut usize,
+    out: &mut Vec<LogSearchMatch>,
+) -> Option<usize> {
+    let mut reader = open_log_reader(path).ok()?;
+    let mut before: VecDeque<String> = VecDeque::with_capacity(search.context);
+    // (indice en `out`, lineas de contexto que faltan)
+    let mut pending: Vec<(usize, usize)> = Vec::new();
+    // Las lineas sin nivel (trazas, reportes) heredan el de la ultima linea que lo tenia.
+    let mut level: Option<String> = (entry.kind == "crash").then(|| "ERROR".to_string());
+    // Igual con la hora: las lineas sin ella quedan en la de la ultima linea que la tenia.
+    let mut clock = LineClock::new(entry);
+    let mut millis = clock.start_millis();
+    let mut found = 0usize;
+    let mut last_match = 0usize;
+    let mut line_no = 0usize;
+    let mut raw = Vec::new();
+    loop {
+        raw.clear();
+        match reader.read_until(b'\n', &mut raw) {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {}
+        }
+        line_no += 1;
+        let decoded = String::from_utf8_lossy(&raw);
+        let text = clip_line(decoded.trim_end_matches(['\r', '\n']));
+        if let Some(found_level) = text_line_level(&text) {
+            level = Some(found_level);
+        }
+        if let Some(stamp) = xml_line_timestamp(&text) {
+            millis = Some(stamp);
+        } else if let Some(time) = text_line_time(&text) {
+            millis = clock.at(time).or(millis);
+        }
+
+        if line_no > skip {
+            for (idx, left) in pending.iter_mut() {
+                out[*idx].after.push(text.clone());
+                *left -= 1;
+            }
+            pending.retain(|(_, left)| *left > 0);
+
+            if found < want {
+                if *budget == 0 {
+                    return Some(line_no - 1);
+                }
+                *budget -= 1;
+                let level_ok = search
+                    .min_level
+                    .map(|min| level.as_deref().map(|l| level_rank(l) >= min).unwrap_or(false))
+                    .unwrap_or(true);
+                if level_ok && search.in_range(millis) && search.matcher.is_match(&text) {
+                    out.push(LogSearchMatch {
+                        kind: entry.kind.clone(),
+                        file: entry.name.clone(),
+                        line: line_no,
+                        text: text.clone(),
+                        level: level.clone(),
+                        before: before.iter().cloned().collect(),
+                        after: Vec::new(),
+                    });
+                    if search.context > 0 {
+                        pending.push((out.len() - 1, search.context));
+                    }
+                    found += 1;
+                    last_match = line_no;
+                }
+            }
+            // Las lineas de contexto posteriores pueden tener resultados propios: la pagina
+            // siguiente retoma justo despues del ultimo resultado.
+            if found >= want && pending.is_empty() {
+                return Some(last_match);
+            }
+        }
+
+        if search.context > 0 {
+            if before.len() == search.context {
+                before.pop_front();
+            }
+            before.push_back(text);
+        }
+    }
+    (found >= want).then_some(last_match)
+}
+
+fn run_search(
+    files: Vec<(PathBuf, InstanceLogEntry)>,
+    query: &LogSearchQuery,
+    search: &Search,
+) -> AppResult<LogSearchPage> {
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+    let (start_idx, mut skip) = match &query.cursor {
+        None => (0, 0),
+        Some(cursor) => {
+            let idx = files
+                .iter()
+                .position(|(_, e)| e.kind == cursor.kind && e.name == cursor.file)
+                .ok_or_else(|| {
+                    AppError::Message("Los logs cambiaron; repeti la busqueda".to_string())
+                })?;
+            (idx, cursor.line)
+        }
+    };
+
+    let mut matches = Vec::new();
+    let mut budget = MAX_LINES_PER_PAGE;
+    let mut next_cursor = None;
+    let mut scanned_files = 0usize;
+    for (idx, (path, entry)) in files.iter().enumerate().skip(start_idx) {
+        scanned_files += 1;
+        let want = limit - matches.len();
+        let stopped = scan_file(path, entry, skip, search, want, &mut budget, &mut matches);
+        skip = 0;
+        if let Some(line) = stopped {
+            next_cursor =
+                Some(LogSearchCursor { kind: entry.kind.clone(), file: entry.name.clone(), line });
+            break;
+        }
+        if budget == 0 {
+            next_cursor = files.get(idx + 1).map(|(_, next)| LogSearchCursor {
+                kind: next.kind.clone(),
+                file: next.name.clone(),
+                line: 0,
+            });
+            break;
+        }
+    }
+
+    Ok(LogSearchPage { matches, next_cursor, scanned_files, total_files: files.len() })
+}
+
+/// Busca un texto o regex en los logs y crash reports de la instancia (incluidos los `.gz`).
+/// Devuelve una pagina de resultados; con `next_cursor` se pide la siguiente.
+pub async fn search_instance_logs_impl(
+    app: &AppHandle,
+    instance_id: String,
+    query: LogSearchQuery,
+) -> AppResult<LogSearchPage> {
+    let base = instance_dir(app, &instance_id);
+    if !base.exists() {
+        return Err("La instancia no existe".to_string().into());
+    }
+    let search = Search {
+        matcher: build_matcher(&query)?,
+        min_level: query.level.as_deref().filter(|l| !l.is_empty()).map(level_rank),
+        context: query.context.unwrap_or(DEFAULT_CONTEXT).min(MAX_CONTEXT),
+        since: query.since,
+        until: query.until,
+    };
+
+    let mut files: Vec<(PathBuf, InstanceLogEntry)> = Vec::new();
+    for (dir, kind) in [(logs_dir(&base), "log"), (crashes_dir(&base), "crash")] {
+        for entry in list_dir_entries(&dir, kind).await {
+            files.push((dir.join(&entry.name), entry));
+        }
+    }
+    files.retain(|(_, entry)| in_window(entry, &query));
+    // Lo mas reciente primero; el nombre desempata para que el cursor sea estable.
+    files.sort_by(|a, b| b.1.modified.cmp(&a.1.modified).then_with(|| a.1.name.cmp(&b.1.name)));
+
+    tokio::task::spawn_blocking(move || run_search(files, &query, &search))
+        .await
+        .map_err(|e| AppError::Message(e.to_string()))?
+}
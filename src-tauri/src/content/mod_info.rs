@@ -0,0 +1,291 @@
+use crate::models::{ModJarDependency, ModJarInfo};
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use toml::Value as TomlValue;
+
+// Iconos mas grandes que esto no se devuelven.
+const MAX_ICON_BYTES: u64 = 128 * 1024;
+// Sube cuando cambia lo que se guarda de cada jar; las entradas de otro formato se releen.
+pub(crate) const CACHE_FORMAT: u32 = 2;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub(crate) struct CachedModInfo {
+    #[serde(default)]
+    pub format: u32,
+    pub size: u64,
+    pub modified: i64,
+    pub info: Option<ModJarInfo>,
+}
+
+impl CachedModInfo {
+    pub(crate) fn new(size: u64, modified: i64, info: Option<ModJarInfo>) -> Self {
+        Self { format: CACHE_FORMAT, size, modified, info }
+    }
+
+    /// True si la entrada sigue valiendo para un jar con este tamano y mtime.
+    pub(crate) fn is_fresh(&self, size: u64, modified: i64) -> bool {
+        self.format == CACHE_FORMAT && self.size == size && self.modified == modified
+    }
+}
+
+pub(crate) type ModInfoCache = HashMap<String, CachedModInfo>;
+
+/// Cache de la instancia, relativo a su carpeta `.launcher/`.
+pub(crate) const CACHE_FILE: &str = "mod_info.cache.json";
+
+pub(crate) fn parse_cache(raw: &str) -> ModInfoCache {
+    serde_json::from_str(raw.trim_start_matches('\u{feff}')).unwrap_or_default()
+}
+
+pub(crate) fn load_cache(path: &Path) -> ModInfoCache {
+    std::fs::read_to_string(path).map(|raw| parse_cache(&raw)).unwrap_or_default()
+}
+
+pub(crate) fn save_cache(path: &Path, cache: &ModInfoCache) {
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Ok(raw) = serde_json::to_string(cache) {
+        let _ = std::fs::write(path, raw);
+    }
+}
+
+fn read_entry(zip: &mut zip::ZipArchive<File>, name: &str, limit: u64) -> Option<Vec<u8>> {
+    let entry = zip.by_name(name).ok()?;
+    if entry.size() > limit {
+        return None;
+    }
+    let mut buf = Vec::with_capacity(entry.size() as usize);
+    entry.take(limit).read_to_end(&mut buf).ok()?;
+    Some(buf)
+}
+
+fn read_text(zip: &mut zip::ZipArchive<File>, name: &str) -> Option<String> {
+    let bytes = read_entry(zip, name, 1024 * 1024)?;
+    Some(String::from_utf8_lossy(&bytes).trim_start_matches('\u{feff}').to_string())
+}
+
+fn icon_data_url(zip: &mut zip::ZipArchive<File>, path: &str) -> Option<String> {
+    let path = path.trim_start_matches('/');
+    let bytes = read_entry(zip, path, MAX_ICON_BYTES)?;
+    let mime = match Path::new(path).extension().and_then(|s| s.to_str()) {
+        Some(ext) if ext.eq_ignore_ascii_case("jpg") || ext.eq_ignore_ascii_case("jpeg") => {
+            "image/jpeg"
+        }
+        Some(ext) if ext.eq_ignore_ascii_case("gif") => "image/gif",
+        _ => "image/png",
+    };
+    Some(format!(
+        "data:{};base64,{}",
+        mime,
+        base64::engine::general_purpose::STANDARD.encode(bytes)
+    ))
+}
+
+fn json_str(value: &JsonValue, key: &str) -> Option<String> {
+    value.get(key).and_then(|v| v.as_str()).map(|s| s.to_string()).filter(|s| !s.is_empty())
+}
+
+// `icon` puede ser una ruta o un mapa tamano -> ruta; nos quedamos con el mas grande.
+fn json_icon_path(value: Option<&JsonValue>) -> Option<String> {
+    match value? {
+        JsonValue::String(s) => Some(s.clone()),
+        JsonValue::Object(map) => map
+            .iter()
+            .filter_map(|(size, path)| Some((size.parse::<u32>().unwrap_or(0), path.as_str()?)))
+            .max_by_key(|(size, _)| *size)
+            .map(|(_, path)| path.to_string()),
+        _ => None,
+    }
+}
+
+fn json_people(value: Option<&JsonValue>) -> Vec<String> {
+    match value {
+        Some(JsonValue::Array(items)) => items
+            .iter()
+            .filter_map(|p| match p {
+                JsonValue::String(s) => Some(s.clone()),
+                JsonValue::Object(_) => json_str(p, "name"),
+                _ => None,
+            })
+            .collect(),
+        // quilt: { "Nombre": "Rol" }
+        Some(JsonValue::Object(map)) => map.keys().cloned().collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn version_range_from_json(value: &JsonValue) -> Option<String> {
+    match value {
+        JsonValue::String(s) => Some(s.clone()),
+        JsonValue::Array(items) => {
+            let parts: Vec<&str> = items.iter().filter_map(|v| v.as_str()).collect();
+            if parts.is_empty() {
+                None
+            } else {
+                Some(parts.join(" || "))
+            }
+        }
+        _ => None,
+    }
+}
+
+fn parse_fabric(raw: &str) -> Option<ModJarInfo> {
+    let json: JsonValue = serde_json::from_str(raw).ok()?;
+    let mut dependencies = Vec::new();
+    for (key, required) in [("depends", true), ("recommends", false)] {
+        if let Some(JsonValue::Object(map)) = json.get(key) {
+            for (id, range) in map {
+                dependencies.push(ModJarDependency {
+                    mod_id: id.clone(),
+                    version_range: version_range_from_json(range),
+                    required,
+                });
+            }
+        }
+    }
+    Some(ModJarInfo {
+        loader: "fabric".to_string(),
+        mod_id: json_str(&json, "id")?,
+        name: json_str(&json, "name"),
+        version: json_str(&json, "version"),
+        authors: json_people(json.get("authors")),
+        description: json_str(&json, "description"),
+        dependencies,
+        icon_path: json_icon_path(json.get("icon")),
+    })
+}
+
+fn parse_quilt(raw: &str) -> Option<ModJarInfo> {
+    let json: JsonValue = serde_json::from_str(raw).ok()?;
+    let loader = json.get("quilt_loader")?;
+    let metadata = loader.get("metadata").cloned().unwrap_or(JsonValue::Null);
+    let mut dependencies = Vec::new();
+    if let Some(JsonValue::Array(items)) = loader.get("depends") {
+        for item in items {
+            let dep = match item {
+                JsonValue::String(id) => {
+                    ModJarDependency { mod_id: id.clone(), version_range: None, required: true }
+                }
+                JsonValue::Object(_) => ModJarDependency {
+                    mod_id: match json_str(item, "id") {
+                        Some(id) => id,
+                        None => continue,
+                    },
+                    version_range: item.get("versions").and_then(version_range_from_json),
+                    required: !item.get("optional").and_then(|v| v.as_bool()).unwrap_or(false),
+                },
+                _ => continue,
+            };
+            dependencies.push(dep);
+        }
+    }
+    Some(ModJarInfo {
+        loader: "quilt".to_string(),
+        mod_id: json_str(loader, "id")?,
+        name: json_str(&metadata, "name"),
+        version: json_str(loader, "version"),
+        authors: json_people(metadata.get("contributors")),
+        description: json_str(&metadata, "description"),
+        dependencies,
+        icon_path: json_icon_path(metadata.get("icon")),
+    })
+}
+
+fn manifest_version(zip: &mut zip::ZipArchive<File>) -> Option<String> {
+    let raw = read_text(zip, "META-INF/MANIFEST.MF")?;
+    raw.lines()
+        .find_map(|line| line.strip_prefix("Implementation-Version:"))
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+fn toml_str(value: &TomlValue, key: &str) -> Option<String> {
+    value.get(key).and_then(|v| v.as_str()).map(|s| s.trim().to_string()).filter(|s| !s.is_empty())
+}
+
+fn parse_mods_toml(zip: &mut zip::ZipArchive<File>, raw: &str, loader: &str) -> Option<ModJarInfo> {
+    let doc: TomlValue = raw.parse().ok()?;
+    let first = doc.get("mods")?.as_array()?.first()?.clone();
+    let mod_id = toml_str(&first, "modId")?;
+
+    // `${file.jarVersion}` se resuelve con el MANIFEST del propio jar.
+    let version = match toml_str(&first, "version") {
+        Some(v) if v.contains("${file.jarVersion}") => manifest_version(zip),
+        other => other,
+    };
+    let authors = toml_str(&first, "authors")
+        .or_else(|| toml_str(&doc, "authors"))
+        .map(|a| a.split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect())
+        .unwrap_or_default();
+
+    let mut dependencies = Vec::new();
+    let deps = doc.get("dependencies").and_then(|d| d.get(&mod_id)).and_then(|d| d.as_array());
+    for dep in deps.into_iter().flatten() {
+        let id = match toml_str(dep, "modId") {
+            Some(id) => id,
+            None => continue,
+        };
+        // Forge usa `mandatory`; NeoForge usa `type = "required"`.
+        let required = match dep.get("mandatory").and_then(|v| v.as_bool()) {
+            Some(mandatory) => mandatory,
+            None => {
+                toml_str(dep, "type").map(|t| t.eq_ignore_ascii_case("required")).unwrap_or(true)
+            }
+        };
+        dependencies.push(ModJarDependency {
+            mod_id: id,
+            version_range: toml_str(dep, "versionRange"),
+            required,
+        });
+    }
+
+    let icon_path = toml_str(&first, "logoFile").or_else(|| toml_str(&doc, "logoFile"));
+    Some(ModJarInfo {
+        loader: loader.to_string(),
+        mod_id,
+        name: toml_str(&first, "displayName"),
+        version,
+        authors,
+        description: toml_str(&first, "description"),
+        dependencies,
+        icon_path,
+    })
+}
+
+/// Lee los metadatos declarados dentro de un jar. Devuelve None si no es un mod reconocible.
+pub(crate) fn read_mod_info(path: &Path) -> Option<ModJarInfo> {
+    let file = File::open(path).ok()?;
+    let mut zip = zip::ZipArchive::new(file).ok()?;
+    if let Some(raw) = read_text(&mut zip, "fabric.mod.json") {
+        // Algunos mods meten saltos de linea crudos dentro de las cadenas.
+        let info = parse_fabric(&raw).or_else(|| parse_fabric(&raw.replace(['\n', '\r'], " ")));
+        if info.is_some() {
+            return info;
+        }
+    }
+    if let Some(raw) = read_text(&mut zip, "quilt.mod.json") {
+        if let Some(info) = parse_quilt(&raw) {
+            return Some(info);
+        }
+    }
+    if let Some(raw) = read_text(&mut zip, "META-INF/neoforge.mods.toml") {
+        if let Some(info) = parse_mods_toml(&mut zip, &raw, "neoforge") {
+            return Some(info);
+        }
+    }
+    let raw = read_text(&mut zip, "META-INF/mods.toml")?;
+    parse_mods_toml(&mut zip, &raw, "forge")
+}
+
+/// Icono de un jar como data URL, leido de `icon_path` (ver `ModJarInfo::icon_path`).
+pub(crate) fn read_mod_icon(path: &Path, icon_path: &str) -> Option<String> {
+    let file = File::open(path).ok()?;
+    let mut zip = zip::ZipArchive::new(file).ok()?;
+    icon_data_url(&mut zip, icon_path)
+}
@@ -0,0 +1,194 @@
+use crate::error::{AppError, AppResult};
+use crate::instances::refresh_instance_mods_cache;
+use crate::models::{ModSet, ModSetApplyResult, ModSetEntry};
+use crate::utils::append_action_log;
+use std::path::PathBuf;
+use tauri::AppHandle;
+use tokio::fs as tokio_fs;
+
+use super::{
+    dependencies, instance_kind_dir, is_allowed_file, load_mod_graph, metadata_dir,
+    set_file_enabled, strip_disabled,
+};
+
+const SET_KINDS: [&str; 3] = ["mods", "resourcepacks", "shaderpacks"];
+
+fn mod_sets_path(app: &AppHandle, instance_id: &str) -> PathBuf {
+    metadata_dir(app, instance_id).join("mod_sets.json")
+}
+
+async fn load_mod_sets(app: &AppHandle, instance_id: &str) -> Vec<ModSet> {
+    match tokio_fs::read_to_string(mod_sets_path(app, instance_id)).await {
+        Ok(raw) => serde_json::from_str(&raw).unwrap_or_default(),
+        Err(_) => Vec::new(),
+    }
+}
+
+async fn save_mod_sets(app: &AppHandle, instance_id: &str, sets: &[ModSet]) -> AppResult<()> {
+    let path = mod_sets_path(app, instance_id);
+    if let Some(parent) = path.parent() {
+        tokio_fs::create_dir_all(parent).await.map_err(|e| AppError::Message(e.to_string()))?;
+    }
+    let raw = serde_json::to_string_pretty(sets).map_err(|e| AppError::Message(e.to_string()))?;
+    tokio_fs::write(path, raw).await.map_err(|e| AppError::Message(e.to_string()))
+}
+
+async fn current_entries(app: &AppHandle, instance_id: &str) -> AppResult<Vec<ModSetEntry>> {
+    let mut out = Vec::new();
+    for kind in SET_KINDS {
+        let dir = instance_kind_dir(app, instance_id, kind)?;
+        let mut rd = match tokio_fs::read_dir(&dir).await {
+            Ok(rd) => rd,
+            Err(_) => continue,
+        };
+        while let Ok(Some(entry)) = rd.next_entry().await {
+            let path = entry.path();
+            if !path.is_file() || !is_allowed_file(kind, &path) {
+                continue;
+            }
+            let (file_name, enabled) = strip_disabled(&entry.file_name().to_string_lossy());
+            out.push(ModSetEntry { kind: kind.to_string(), file_name, enabled });
+        }
+    }
+    out.sort_by(|a, b| (&a.kind, &a.file_name).cmp(&(&b.kind, &b.file_name)));
+    Ok(out)
+}
+
+async fn find_mod_set(app: &AppHandle, instance_id: &str, name: &str) -> AppResult<ModSet> {
+    load_mod_sets(app, instance_id)
+        .await
+        .into_iter()
+        .find(|s| s.name.eq_ignore_ascii_case(name.trim()))
+        .ok_or_else(|| AppError::Message("Set no encontrado".to_string()))
+}
+
+pub async fn list_mod_sets_impl(app: &AppHandle, instance_id: String) -> AppResult<Vec<ModSet>> {
+    let mut sets = load_mod_sets(app, &instance_id).await;
+    sets.sort_by_key(|s| s.name.to_lowercase());
+    Ok(sets)
+}
+
+/// Guarda el estado actual de la instancia con ese nombre (reemplaza un set con el mismo nombre).
+pub async fn create_mod_set_impl(
+    app: &AppHandle,
+    instance_id: String,
+    name: String,
+) -> AppResult<ModSet> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("El nombre del set no puede estar vacio".to_string().into());
+    }
+    let set = ModSet {
+        name: name.clone(),
+        entries: current_entries(app, &instance_id).await?,
+        created_at: chrono::Utc::now().timestamp_millis(),
+    };
+    let mut sets = load_mod_sets(app, &instance_id).await;
+    sets.retain(|s| !s.name.eq_ignore_ascii_case(&name));
+    sets.push(set.clone());
+    save_mod_sets(app, &instance_id, &sets).await?;
+    let _ = append_action_log(
+        app,
+        &format!(
+            "mod_set_create instance={} name={} files={}",
+            instance_id,
+            name,
+            set.entries.len()
+        ),
+    )
+    .await;
+    Ok(set)
+}
+
+pub async fn delete_mod_set_impl(
+    app: &AppHandle,
+    instance_id: String,
+    name: String,
+) -> AppResult<()> {
+    let mut sets = load_mod_sets(app, &instance_id).await;
+    let before = sets.len();
+    sets.retain(|s| !s.name.eq_ignore_ascii_case(name.trim()));
+    if sets.len() == before {
+        return Err("Set no encontrado".to_string().into());
+    }
+    save_mod_sets(app, &instance_id, &sets).await
+}
+
+// Simula el set sobre el grafo de mods y lista las dependencias que quedarian desactivadas.
+async fn dependency_warnings(
+    app: &AppHandle,
+    instance_id: &str,
+    set: &ModSet,
+) -> AppResult<Vec<String>> {
+    let mut nodes = load_mod_graph(app, instance_id).await?;
+    for node in nodes.iter_mut() {
+        if let Some(entry) =
+            set.entries.iter().find(|e| e.kind == "mods" && e.file_name == node.file_name)
+        {
+            node.enabled = entry.enabled;
+        }
+    }
+    Ok(dependencies::broken_requirements(&nodes)
+        .into_iter()
+        .map(|(mod_idx, dep_idx)| {
+            format!(
+                "{} necesita {}, que queda desactivado",
+                nodes[mod_idx].file_name, nodes[dep_idx].file_name
+            )
+        })
+        .collect())
+}
+
+pub async fn check_mod_set_impl(
+    app: &AppHandle,
+    instance_id: String,
+    name: String,
+) -> AppResult<Vec<String>> {
+    let set = find_mod_set(app, &instance_id, &name).await?;
+    dependency_warnings(app, &instance_id, &set).await
+}
+
+/// Aplica el set renombrando a/desde `.disabled`. Los archivos que el set no conoce no se tocan.
+/// Si desactiva dependencias requeridas falla salvo que se pase `force`.
+pub async fn apply_mod_set_impl(
+    app: &AppHandle,
+    instance_id: String,
+    name: String,
+    force: Option<bool>,
+) -> AppResult<ModSetApplyResult> {
+    let set = find_mod_set(app, &instance_id, &name).await?;
+    let warnings = dependency_warnings(app, &instance_id, &set).await?;
+    if !warnings.is_empty() && !force.unwrap_or(false) {
+        return Err(format!("El set rompe dependencias: {}", warnings.join("; ")).into());
+    }
+
+    let current = current_entries(app, &instance_id).await?;
+    let mut changed = 0u32;
+    let mut missing = Vec::new();
+    for entry in set.entries.iter() {
+        let existing =
+            current.iter().find(|c| c.kind == entry.kind && c.file_name == entry.file_name);
+        match existing {
+            Some(c) if c.enabled == entry.enabled => {}
+            Some(_) => {
+                let dir = instance_kind_dir(app, &instance_id, &entry.kind)?;
+                set_file_enabled(&dir, &entry.file_name, entry.enabled).await?;
+                changed += 1;
+            }
+            None => missing.push(entry.file_name.clone()),
+        }
+    }
+    let _ = refresh_instance_mods_cache(app, &instance_id).await;
+    let _ = append_action_log(
+        app,
+        &format!(
+            "mod_set_apply instance={} name={} changed={} missing={}",
+            instance_id,
+            set.name,
+            changed,
+            missing.len()
+        ),
+    )
+    .await;
+    Ok(ModSetApplyResult { changed, missing, warnings })
+}
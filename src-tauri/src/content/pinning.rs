@@ -0,0 +1,100 @@
+use crate::error::AppResult;
+use crate::models::ModMetadataEntry;
+use crate::utils::append_action_log;
+use std::collections::HashMap;
+use std::path::Path;
+use tauri::AppHandle;
+
+use super::mod_info::read_mod_info;
+use super::{
+    instance_kind_dir, load_mods_metadata, metadata_kind_matches, save_mods_metadata,
+    strip_disabled,
+};
+
+const PIN_KINDS: [&str; 3] = ["mods", "resourcepacks", "shaderpacks"];
+
+/// Entrada fijada que coincide con el archivo o con el mismo proyecto de Modrinth.
+pub(crate) fn find_pinned<'a>(
+    metadata: &'a [ModMetadataEntry],
+    kind: &str,
+    project_id: Option<&str>,
+    file_name: &str,
+) -> Option<&'a ModMetadataEntry> {
+    metadata.iter().filter(|m| m.pinned && metadata_kind_matches(&m.kind, kind)).find(|m| {
+        m.file_name == file_name || (project_id.is_some() && m.project_id.as_deref() == project_id)
+    })
+}
+
+/// Rutas relativas a la instancia (`mods/x.jar`) de los archivos fijados.
+pub(crate) fn pinned_paths(metadata: &[ModMetadataEntry]) -> Vec<String> {
+    metadata
+        .iter()
+        .filter(|m| m.pinned)
+        .filter_map(|m| {
+            let kind = PIN_KINDS.iter().find(|k| metadata_kind_matches(&m.kind, k))?;
+            Some(format!("{}/{}", kind, m.file_name))
+        })
+        .collect()
+}
+
+/// Id de mod declarado en cada jar fijado de `mods_dir`, con el nombre del archivo fijado.
+/// Sirve para reconocer el mismo mod aunque llegue con otro nombre de archivo.
+pub(crate) fn pinned_mod_ids(
+    mods_dir: &Path,
+    metadata: &[ModMetadataEntry],
+) -> HashMap<String, String> {
+    metadata
+        .iter()
+        .filter(|m| m.pinned && metadata_kind_matches(&m.kind, "mods"))
+        .filter_map(|m| {
+            let path =
+                [mods_dir.join(&m.file_name), mods_dir.join(format!("{}.disabled", m.file_name))]
+                    .into_iter()
+                    .find(|p| p.is_file())?;
+            Some((read_mod_info(&path)?.mod_id, m.file_name.clone()))
+        })
+        .collect()
+}
+
+pub async fn set_content_pinned_impl(
+    app: &AppHandle,
+    instance_id: &str,
+    kind: &str,
+    file_name: &str,
+    pinned: bool,
+) -> AppResult<()> {
+    let dir = instance_kind_dir(app, instance_id, kind)?;
+    let (clean, _) = strip_disabled(file_name);
+    if clean.contains('/') || clean.contains('\\') || clean.contains("..") {
+        return Err("Nombre de archivo invalido".to_string().into());
+    }
+    if !dir.join(&clean).is_file() && !dir.join(format!("{}.disabled", clean)).is_file() {
+        return Err("Archivo no encontrado".to_string().into());
+    }
+
+    let mut metadata = load_mods_metadata(app, instance_id).await;
+    match metadata.iter_mut().find(|m| m.file_name == clean && metadata_kind_matches(&m.kind, kind))
+    {
+        Some(entry) => entry.pinned = pinned,
+        None if pinned => metadata.push(ModMetadataEntry {
+            file_name: clean.clone(),
+            version_id: None,
+            project_id: None,
+            dependencies: Vec::new(),
+            source: None,
+            kind: Some(kind.to_string()),
+            pinned: true,
+        }),
+        None => return Ok(()),
+    }
+    save_mods_metadata(app, instance_id, &metadata).await;
+    let _ = append_action_log(
+        app,
+        &format!(
+            "content_pin instance={} kind={} file={} pinned={}",
+            instance_id, kind, clean, pinned
+        ),
+    )
+    .await;
+    Ok(())
+}
@@ -1,13 +1,20 @@
+use crate::launcher::is_launch_log;
 use crate::utils::{append_action_log, get_launcher_dir};
 use reqwest::multipart::{Form, Part};
 use serde::Serialize;
 use std::fs;
-use std::io::Write;
-use std::path::PathBuf;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
 use tauri::AppHandle;
 use zip::write::FileOptions;
 
 use crate::error::AppResult;
+
+mod redact;
+
+use redact::Redactor;
+pub use redact::{get_redaction_config_impl, set_redaction_config_impl};
+
 #[derive(Serialize)]
 struct DiagnosticInfo {
     timestamp: String,
@@ -20,21 +27,86 @@ struct DiagnosticInfo {
     instance_id: Option<String>,
 }
 
+// Solo entra texto: cualquier otro archivo podria llevar datos que la redaccion no ve.
+const TEXT_EXTENSIONS: [&str; 7] = ["log", "txt", "json", "xml", "cfg", "properties", "toml"];
+
+fn add_text_to_zip(
+    zip: &mut zip::ZipWriter<fs::File>,
+    zip_name: &str,
+    text: &str,
+    redactor: &mut Redactor,
+    options: FileOptions,
+) -> AppResult<()> {
+    let redacted = redactor.redact(zip_name, text);
+    zip.start_file(zip_name, options)
+        .map_err(|e| crate::error::AppError::Message(e.to_string()))?;
+    zip.write_all(redacted.as_bytes())
+        .map_err(|e| crate::error::AppError::Message(e.to_string()))?;
+    Ok(())
+}
+
 fn add_file_to_zip(
     zip: &mut zip::ZipWriter<fs::File>,
     file_path: &std::path::Path,
     zip_name: &str,
+    redactor: &mut Redactor,
     options: FileOptions,
 ) -> AppResult<()> {
     if !file_path.exists() || !file_path.is_file() {
         return Ok(());
     }
-    zip.start_file(zip_name, options)
-        .map_err(|e| crate::error::AppError::Message(e.to_string()))?;
-    let mut f =
+    // Los logs rotados se guardan descomprimidos para poder redactarlos.
+    let (zip_name, gzipped) = match zip_name.strip_suffix(".gz") {
+        Some(name) => (name, true),
+        None => (zip_name, false),
+    };
+    let ext = std::path::Path::new(zip_name)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+    if !TEXT_EXTENSIONS.contains(&ext.as_str()) {
+        redactor.omit(zip_name);
+        return Ok(());
+    }
+    let mut data = Vec::new();
+    let f =
         fs::File::open(file_path).map_err(|e| crate::error::AppError::Message(e.to_string()))?;
-    std::io::copy(&mut f, zip).map_err(|e| crate::error::AppError::Message(e.to_string()))?;
-    Ok(())
+    let read = if gzipped {
+        flate2::read::GzDecoder::new(f).read_to_end(&mut data)
+    } else {
+        std::io::BufReader::new(f).read_to_end(&mut data)
+    };
+    if read.is_err() || data.contains(&0) {
+        redactor.omit(zip_name);
+        return Ok(());
+    }
+    add_text_to_zip(zip, zip_name, &String::from_utf8_lossy(&data), redactor, options)
+}
+
+// Log de lanzamiento mas reciente de cada instancia; la fecha va en el nombre.
+fn latest_launch_logs(base: &Path) -> Vec<(String, PathBuf)> {
+    let instances = match fs::read_dir(base.join("instances")) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+    let mut out = Vec::new();
+    for instance in instances.flatten() {
+        let logs_dir = instance.path().join("logs");
+        let latest = match fs::read_dir(&logs_dir) {
+            Ok(entries) => entries
+                .flatten()
+                .map(|e| e.file_name().to_string_lossy().to_string())
+                .filter(|name| is_launch_log(name))
+                .max(),
+            Err(_) => None,
+        };
+        if let Some(name) = latest {
+            let id = instance.file_name().to_string_lossy().to_string();
+            out.push((format!("instances/{}/logs/{}", id, name), logs_dir.join(name)));
+        }
+    }
+    out
 }
 
 fn add_dir_recursive(
@@ -42,6 +114,7 @@ fn add_dir_recursive(
     base: &std::path::Path,
     dir: &std::path::Path,
     prefix: &str,
+    redactor: &mut Redactor,
     options: FileOptions,
 ) -> AppResult<()> {
     for entry in fs::read_dir(dir).map_err(|e| crate::error::AppError::Message(e.to_string()))? {
@@ -56,14 +129,28 @@ fn add_dir_recursive(
         if path.is_dir() {
             zip.add_directory(format!("{}/", zip_name), options)
                 .map_err(|e| crate::error::AppError::Message(e.to_string()))?;
-            add_dir_recursive(zip, base, &path, prefix, options)?;
+            add_dir_recursive(zip, base, &path, prefix, redactor, options)?;
         } else {
-            add_file_to_zip(zip, &path, &zip_name, options)?;
+            add_file_to_zip(zip, &path, &zip_name, redactor, options)?;
         }
     }
     Ok(())
 }
 
+fn add_redaction_manifest(
+    zip: &mut zip::ZipWriter<fs::File>,
+    redactor: Redactor,
+    options: FileOptions,
+) -> AppResult<()> {
+    let manifest = serde_json::to_string_pretty(&redactor.finish())
+        .map_err(|e| crate::error::AppError::Message(e.to_string()))?;
+    zip.start_file("redaction-manifest.json", options)
+        .map_err(|e| crate::error::AppError::Message(e.to_string()))?;
+    zip.write_all(manifest.as_bytes())
+        .map_err(|e| crate::error::AppError::Message(e.to_string()))?;
+    Ok(())
+}
+
 fn support_endpoint() -> AppResult<String> {
     let endpoint = std::env::var("NEWEN_SUPPORT_ENDPOINT").unwrap_or_default();
     let trimmed = endpoint.trim().to_string();
@@ -140,7 +227,7 @@ pub async fn generate_diagnostic_report_impl(app: &AppHandle) -> AppResult<Strin
     let instances_file = base.join("instances.json");
     let instance_count = if instances_file.exists() {
         if let Ok(raw) = fs::read_to_string(&instances_file) {
-            serde_json::from_str::<Vec<serde_json::Value>>(&raw).map(|v| v.len()).unwrap_or(0)
+            crate::instances::parse_instances_file(&raw).map(|(v, _)| v.len()).unwrap_or(0)
         } else {
             0
         }
@@ -158,6 +245,7 @@ pub async fn generate_diagnostic_report_impl(app: &AppHandle) -> AppResult<Strin
         instance_id: None,
     };
 
+    let mut redactor = Redactor::new(&get_redaction_config_impl(app).await?)?;
     let file =
         fs::File::create(&zip_path).map_err(|e| crate::error::AppError::Message(e.to_string()))?;
     let mut zip = zip::ZipWriter::new(file);
@@ -165,17 +253,20 @@ pub async fn generate_diagnostic_report_impl(app: &AppHandle) -> AppResult<Strin
 
     let json = serde_json::to_string_pretty(&info)
         .map_err(|e| crate::error::AppError::Message(e.to_string()))?;
-    zip.start_file("diagnostic.json", options)
-        .map_err(|e| crate::error::AppError::Message(e.to_string()))?;
-    zip.write_all(json.as_bytes()).map_err(|e| crate::error::AppError::Message(e.to_string()))?;
+    add_text_to_zip(&mut zip, "diagnostic.json", &json, &mut redactor, options)?;
 
-    add_file_to_zip(&mut zip, &instances_file, "instances.json", options)?;
+    add_file_to_zip(&mut zip, &instances_file, "instances.json", &mut redactor, options)?;
 
     let logs_dir = base.join("logs");
     if logs_dir.exists() {
-        add_dir_recursive(&mut zip, &logs_dir, &logs_dir, "logs", options)?;
+        add_dir_recursive(&mut zip, &logs_dir, &logs_dir, "logs", &mut redactor, options)?;
+    }
+    // Los lanzamientos escriben su log en la carpeta de cada instancia.
+    for (zip_name, path) in latest_launch_logs(&base) {
+        add_file_to_zip(&mut zip, &path, &zip_name, &mut redactor, options)?;
     }
 
+    add_redaction_manifest(&mut zip, redactor, options)?;
     zip.finish().map_err(|e| crate::error::AppError::Message(e.to_string()))?;
     let _ =
         append_action_log(app, &format!("diagnostic_report path={}", zip_path.to_string_lossy()))
@@ -202,7 +293,7 @@ pub async fn generate_diagnostic_report_for_instance_impl(
     let instances_file = base.join("instances.json");
     let instance_count = if instances_file.exists() {
         if let Ok(raw) = fs::read_to_string(&instances_file) {
-            serde_json::from_str::<Vec<serde_json::Value>>(&raw).map(|v| v.len()).unwrap_or(0)
+            crate::instances::parse_instances_file(&raw).map(|(v, _)| v.len()).unwrap_or(0)
         } else {
             0
         }
@@ -220,6 +311,7 @@ pub async fn generate_diagnostic_report_for_instance_impl(
         instance_id: Some(instance_id.clone()),
     };
 
+    let mut redactor = Redactor::new(&get_redaction_config_impl(app).await?)?;
     let file =
         fs::File::create(&zip_path).map_err(|e| crate::error::AppError::Message(e.to_string()))?;
     let mut zip = zip::ZipWriter::new(file);
@@ -227,15 +319,13 @@ pub async fn generate_diagnostic_report_for_instance_impl(
 
     let json = serde_json::to_string_pretty(&info)
         .map_err(|e| crate::error::AppError::Message(e.to_string()))?;
-    zip.start_file("diagnostic.json", options)
-        .map_err(|e| crate::error::AppError::Message(e.to_string()))?;
-    zip.write_all(json.as_bytes()).map_err(|e| crate::error::AppError::Message(e.to_string()))?;
+    add_text_to_zip(&mut zip, "diagnostic.json", &json, &mut redactor, options)?;
 
-    add_file_to_zip(&mut zip, &instances_file, "instances.json", options)?;
+    add_file_to_zip(&mut zip, &instances_file, "instances.json", &mut redactor, options)?;
 
     let logs_dir = base.join("logs");
     if logs_dir.exists() {
-        add_dir_recursive(&mut zip, &logs_dir, &logs_dir, "logs", options)?;
+        add_dir_recursive(&mut zip, &logs_dir, &logs_dir, "logs", &mut redactor, options)?;
     }
 
     let instance_logs = instance_dir.join("logs");
@@ -245,6 +335,7 @@ pub async fn generate_diagnostic_report_for_instance_impl(
             &instance_logs,
             &instance_logs,
             &format!("instances/{}/logs", instance_id),
+            &mut redactor,
             options,
         )?;
     }
@@ -256,10 +347,12 @@ pub async fn generate_diagnostic_report_for_instance_impl(
             &instance_crash,
             &instance_crash,
             &format!("instances/{}/crash-reports", instance_id),
+            &mut redactor,
             options,
         )?;
     }
 
+    add_redaction_manifest(&mut zip, redactor, options)?;
     zip.finish().map_err(|e| crate::error::AppError::Message(e.to_string()))?;
     let _ = append_action_log(
         app,
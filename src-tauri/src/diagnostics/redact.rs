@@ -0,0 +1,264 @@
+use crate::error::{AppError, AppResult};
+use crate::models::{RedactedFile, RedactionConfig, RedactionManifest, RedactionRule};
+use crate::utils::get_launcher_dir;
+use regex::{Captures, Regex};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use tauri::AppHandle;
+use tokio::fs as tokio_fs;
+
+// Recibe el valor encontrado y los caracteres justo antes y despues de el.
+type AcceptFn = fn(&str, Option<char>, Option<char>) -> bool;
+
+struct CompiledRule {
+    name: String,
+    regex: Regex,
+    replacement: String,
+    // Filtro extra sobre el valor encontrado (las versiones tipo 14.23.5.2859 no son IPs).
+    accept: Option<AcceptFn>,
+}
+
+// En todas las reglas el grupo `pre` se conserva y solo se reemplaza el valor.
+const TOKEN_PATTERNS: [&str; 4] = [
+    r"(?P<pre>--accessToken\s+)\S+",
+    r#"(?i)(?P<pre>"(?:access|refresh|id)_?token"\s*:\s*")[^"]+"#,
+    r"(?i)(?P<pre>Bearer\s+)[A-Za-z0-9\-._~+/]+=*",
+    r"eyJ[A-Za-z0-9_-]{8,}\.[A-Za-z0-9_-]{8,}\.[A-Za-z0-9_-]+",
+];
+const USER_PATTERNS: [&str; 3] = [
+    r"(?i)(?P<pre>[\\/](?:home|Users)[\\/]+)[^\\/\s:;'\x22]+",
+    r"(?P<pre>--username\s+)\S+",
+    r"(?P<pre>Setting user:\s*)\S+",
+];
+const IP_PATTERNS: [&str; 2] =
+    [r"\b(?:\d{1,3}\.){3}\d{1,3}\b", r"\b(?:[0-9a-fA-F]{1,4}:){7}[0-9a-fA-F]{1,4}\b"];
+const SESSION_PATTERNS: [&str; 4] = [
+    r"(?P<pre>--(?:session|uuid|xuid|clientId)\s+)\S+",
+    r"(?i)(?P<pre>session[_ ]?id\s*[:=]\s*)[A-Za-z0-9\-_.]{8,}",
+    r"token:[A-Za-z0-9\-_.]+:[0-9a-fA-F\-]+",
+    r#"(?i)(?P<pre>"(?:uuid|xuid|client_?id)"\s*:\s*")[^"]+"#,
+];
+
+fn public_ipv4(value: &str, before: Option<char>, after: Option<char>) -> bool {
+    // `mod-2.0.1.3`, `mc+1.20.4.1`, `1.2.3.4.5` o `1.2.3.4-beta` son versiones, no direcciones.
+    // Una letra pegada ya la descarta el `\b` del patron (`v1.20.4.1`).
+    if before.map(|c| matches!(c, '-' | '+' | '.')).unwrap_or(false)
+        || after.map(|c| matches!(c, '-' | '+')).unwrap_or(false)
+    {
+        return false;
+    }
+    let octets: Vec<u32> = value.split('.').filter_map(|o| o.parse().ok()).collect();
+    if octets.len() != 4 || octets.iter().any(|o| *o > 255) {
+        return false;
+    }
+    // Loopback y 0.0.0.0 no identifican a nadie y ayudan a leer el reporte.
+    !(octets[0] == 127 || octets == [0, 0, 0, 0])
+}
+
+fn compile(pattern: &str, name: &str) -> AppResult<Regex> {
+    Regex::new(pattern)
+        .map_err(|e| AppError::Message(format!("Regla de redaccion invalida ({}): {}", name, e)))
+}
+
+// Nombre de la cuenta del sistema, para quitarlo tambien fuera de las rutas.
+fn os_username() -> Option<String> {
+    let from_home = ["HOME", "USERPROFILE"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .filter_map(|home| PathBuf::from(home).file_name().map(|n| n.to_string_lossy().to_string()))
+        .next();
+    let name =
+        std::env::var("USERNAME").ok().or_else(|| std::env::var("USER").ok()).or(from_home)?;
+    // Nombres muy cortos aparecen como parte de cualquier palabra.
+    (name.chars().count() >= 3 && name != "root").then_some(name)
+}
+
+/// Aplica las reglas a cada archivo que entra al reporte y anota lo que quito.
+pub(crate) struct Redactor {
+    rules: Vec<CompiledRule>,
+    manifest: RedactionManifest,
+}
+
+impl Redactor {
+    pub(crate) fn new(config: &RedactionConfig) -> AppResult<Self> {
+        let mut rules = Vec::new();
+        let mut builtin = |name: &str, patterns: &[&str], replacement: &str, enabled: bool| {
+            if !enabled {
+                return Ok::<(), AppError>(());
+            }
+            for pattern in patterns {
+                rules.push(CompiledRule {
+                    name: name.to_string(),
+                    regex: compile(pattern, name)?,
+                    replacement: replacement.to_string(),
+                    accept: (*pattern == IP_PATTERNS[0]).then_some(public_ipv4 as AcceptFn),
+                });
+            }
+            Ok(())
+        };
+        builtin("access_token", &TOKEN_PATTERNS, "<token>", config.access_tokens)?;
+        builtin("session_id", &SESSION_PATTERNS, "<sesion>", config.session_ids)?;
+        builtin("username", &USER_PATTERNS, "<usuario>", config.usernames)?;
+        builtin("ip", &IP_PATTERNS, "<ip>", config.ip_addresses)?;
+        if config.usernames {
+            if let Some(user) = os_username() {
+                rules.push(CompiledRule {
+                    name: "username".to_string(),
+                    regex: compile(&format!(r"(?i)\b{}\b", regex::escape(&user)), "username")?,
+                    replacement: "<usuario>".to_string(),
+                    accept: None,
+                });
+            }
+        }
+        for rule in &config.custom {
+            rules.push(compile_custom(rule)?);
+        }
+
+        let mut names: Vec<String> = Vec::new();
+        for rule in &rules {
+            if !names.contains(&rule.name) {
+                names.push(rule.name.clone());
+            }
+        }
+        Ok(Self { rules, manifest: RedactionManifest { rules: names, ..Default::default() } })
+    }
+
+    /// Devuelve `text` sin los datos sensibles y registra los reemplazos de `path`.
+    pub(crate) fn redact(&mut self, path: &str, text: &str) -> String {
+        let mut out = text.to_string();
+        let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+        for rule in &self.rules {
+            let mut hits = 0usize;
+            let haystack = out.as_str();
+            let replaced = rule.regex.replace_all(haystack, |caps: &Captures| {
+                let whole = &caps[0];
+                let (start, end) = caps.get(0).map(|m| (m.start(), m.end())).unwrap_or_default();
+                let pre = caps.name("pre").map(|m| m.as_str()).unwrap_or("");
+                let value = &whole[pre.len()..];
+                let before = haystack[..start].chars().next_back();
+                let after = haystack[end..].chars().next();
+                if rule.accept.map(|accept| !accept(value, before, after)).unwrap_or(false) {
+                    return whole.to_string();
+                }
+                hits += 1;
+                format!("{}{}", pre, rule.replacement)
+            });
+            if hits > 0 {
+                out = replaced.into_owned();
+                *counts.entry(rule.name.clone()).or_insert(0) += hits;
+            }
+        }
+        if !counts.is_empty() {
+            self.manifest.total += counts.values().sum::<usize>();
+            self.manifest.files.push(RedactedFile { path: path.to_string(), counts });
+        }
+        out
+    }
+
+    pub(crate) fn omit(&mut self, path: &str) {
+        self.manifest.omitted.push(path.to_string());
+    }
+
+    pub(crate) fn finish(self) -> RedactionManifest {
+        self.manifest
+    }
+}
+
+fn compile_custom(rule: &RedactionRule) -> AppResult<CompiledRule> {
+    let name = rule.name.trim();
+    if name.is_empty() || rule.pattern.is_empty() {
+        return Err("Las reglas de redaccion necesitan nombre y patron".to_string().into());
+    }
+    Ok(CompiledRule {
+        name: name.to_string(),
+        regex: compile(&rule.pattern, name)?,
+        replacement: rule.replacement.clone().unwrap_or_else(|| "<redactado>".to_string()),
+        accept: None,
+    })
+}
+
+fn config_path(app: &AppHandle) -> PathBuf {
+    get_launcher_dir(app).join("reports").join("redaction.json")
+}
+
+pub async fn get_redaction_config_impl(app: &AppHandle) -> AppResult<RedactionConfig> {
+    match tokio_fs::read_to_string(config_path(app)).await {
+        Ok(raw) => Ok(serde_json::from_str(raw.trim_start_matches('\u{feff}')).unwrap_or_default()),
+        Err(_) => Ok(RedactionConfig::default()),
+    }
+}
+
+pub async fn set_redaction_config_impl(
+    app: &AppHandle,
+    config: RedactionConfig,
+) -> AppResult<RedactionConfig> {
+    // Una regla que no compila romperia todos los reportes siguientes.
+    Redactor::new(&config)?;
+    let path = config_path(app);
+    if let Some(parent) = path.parent() {
+        tokio_fs::create_dir_all(parent).await.map_err(|e| AppError::Message(e.to_string()))?;
+    }
+    let raw =
+        serde_json::to_string_pretty(&config).map_err(|e| AppError::Message(e.to_string()))?;
+    tokio_fs::write(path, raw).await.map_err(|e| AppError::Message(e.to_string()))?;
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn redactor() -> Redactor {
+        Redactor::new(&RedactionConfig::default()).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    #[test]
+    fn public_ipv4_skips_local_and_invalid_addresses() {
+        assert!(public_ipv4("93.184.216.34", Some(' '), Some(':')));
+        assert!(!public_ipv4("127.0.0.1", None, None));
+        assert!(!public_ipv4("0.0.0.0", None, None));
+        assert!(!public_ipv4("300.1.1.1", None, None));
+    }
+
+    #[test]
+    fn public_ipv4_skips_version_context() {
+        assert!(!public_ipv4("2.0.1.3", Some('-'), Some('.')));
+        assert!(!public_ipv4("1.20.4.1", Some('+'), None));
+        assert!(!public_ipv4("2.3.4.5", Some('.'), None));
+        assert!(!public_ipv4("1.2.3.4", None, Some('-')));
+    }
+
+    #[test]
+    fn redacts_addresses_but_not_versions() {
+        let mut redactor = redactor();
+        let out = redactor.redact(
+            "latest.log",
+            "Connecting to 93.184.216.34:25565, loaded mod-2.0.1.3.jar v1.20.4.1 and 1.2.3.4-beta",
+        );
+        assert_eq!(
+            out,
+            "Connecting to <ip>:25565, loaded mod-2.0.1.3.jar v1.20.4.1 and 1.2.3.4-beta"
+        );
+    }
+
+    #[test]
+    fn redacts_tokens_and_counts_them() {
+        let mut redactor = redactor();
+        let out = redactor.redact(
+            "launch.log",
+            "--accessToken abc.def-123 --session xyz Authorization: Bearer Zm9vYmFy==",
+        );
+        assert_eq!(out, "--accessToken <token> --session <sesion> Authorization: Bearer <token>");
+        let manifest = redactor.finish();
+        assert_eq!(manifest.total, 3);
+        assert_eq!(manifest.files[0].path, "launch.log");
+        assert_eq!(manifest.files[0].counts.get("access_token"), Some(&2));
+    }
+
+    #[test]
+    fn custom_rule_needs_name_and_pattern() {
+        let rule =
+            RedactionRule { name: " ".to_string(), pattern: "x".to_string(), replacement: None };
+        assert!(compile_custom(&rule).is_err());
+    }
+}
@@ -1,10 +1,30 @@
-use crate::models::{Instance, InstanceCreateRequest, InstanceSummary, InstanceUpdateRequest};
+use crate::models::{
+    Instance, InstanceCreateRequest, InstanceDuplicateRequest, InstanceSettings, InstanceSummary,
+    InstanceUpdateRequest,
+};
+use crate::state::AppState;
 use crate::utils::get_launcher_dir;
 use std::path::{Path, PathBuf};
-use tauri::AppHandle;
+use tauri::{AppHandle, Manager};
 use tokio::fs as tokio_fs;
 use uuid::Uuid;
 
+mod shared;
+mod store;
+mod trash;
+mod upgrade;
+
+pub use shared::set_instance_shared_folders_impl;
+pub(crate) use shared::{is_dir_link, repair_shared_folders};
+pub(crate) use store::parse_instances_file;
+pub use store::InstanceStore;
+pub use trash::{
+    get_trash_config_impl, list_trashed_instances_impl, purge_trashed_instances_impl,
+    restore_trashed_instance_impl, set_trash_config_impl,
+};
+pub(crate) use upgrade::install_loader;
+pub use upgrade::{apply_instance_upgrade_impl, preview_instance_upgrade_impl};
+
 use crate::error::AppResult;
 fn instances_file(app: &AppHandle) -> PathBuf {
     get_launcher_dir(app).join("instances.json")
@@ -50,20 +70,16 @@ fn now_millis() -> i64 {
 }
 
 async fn load_instances(app: &AppHandle) -> AppResult<Vec<Instance>> {
-    let path = instances_file(app);
-    if !path.exists() {
-        return Ok(Vec::new());
-    }
-    let raw = tokio_fs::read_to_string(&path)
-        .await
-        .map_err(|e| crate::error::AppError::Message(e.to_string()))?;
-    let trimmed = raw.trim_start_matches('\u{feff}').trim();
-    if trimmed.is_empty() {
-        return Ok(Vec::new());
-    }
-    let parsed: Vec<Instance> = serde_json::from_str(trimmed)
-        .map_err(|e| crate::error::AppError::Message(format!("instances.json invalido: {}", e)))?;
-    Ok(parsed)
+    let state = app.state::<AppState>();
+    state.instances.load(&instances_file(app)).await
+}
+
+async fn update_instances<T, F>(app: &AppHandle, f: F) -> AppResult<T>
+where
+    F: FnOnce(&mut Vec<Instance>) -> AppResult<T>,
+{
+    let state = app.state::<AppState>();
+    state.instances.update(&instances_file(app), f).await
 }
 
 pub async fn get_instance_impl(app: &AppHandle, instance_id: &str) -> AppResult<Instance> {
@@ -74,23 +90,35 @@ pub async fn get_instance_impl(app: &AppHandle, instance_id: &str) -> AppResult<
         .ok_or_else(|| crate::error::AppError::Message("Instancia no encontrada".to_string()))
 }
 
-async fn save_instances(app: &AppHandle, instances: &[Instance]) -> AppResult<()> {
-    let path = instances_file(app);
-    if let Some(parent) = path.parent() {
-        tokio_fs::create_dir_all(parent)
-            .await
-            .map_err(|e| crate::error::AppError::Message(e.to_string()))?;
+fn normalize_instance_settings(settings: InstanceSettings) -> AppResult<Option<InstanceSettings>> {
+    let mut out = settings;
+    if let Some(memory) = out.memory.as_ref() {
+        if memory.max_gb == 0 {
+            return Err("La memoria maxima debe ser al menos 1 GB".to_string().into());
+        }
+        if memory.min_gb > memory.max_gb {
+            return Err("La memoria minima no puede superar la maxima".to_string().into());
+        }
     }
-    let tmp = path.with_extension("tmp");
-    let json = serde_json::to_string_pretty(instances)
-        .map_err(|e| crate::error::AppError::Message(e.to_string()))?;
-    tokio_fs::write(&tmp, json)
-        .await
-        .map_err(|e| crate::error::AppError::Message(e.to_string()))?;
-    tokio_fs::rename(&tmp, &path)
-        .await
-        .map_err(|e| crate::error::AppError::Message(e.to_string()))?;
-    Ok(())
+    if let Some(resolution) = out.resolution.as_ref() {
+        if resolution.width == 0 || resolution.height == 0 {
+            return Err("Resolucion invalida".to_string().into());
+        }
+    }
+    out.java_args = out.java_args.map(|a| a.trim().to_string()).filter(|a| !a.is_empty());
+    out.java_path = out.java_path.map(|p| p.trim().to_string()).filter(|p| !p.is_empty());
+    if let Some(path) = out.java_path.as_ref() {
+        if !Path::new(path).exists() {
+            return Err("Ruta de Java invalida".to_string().into());
+        }
+    }
+    if out.max_fps == Some(0) {
+        out.max_fps = None;
+    }
+    if out.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(out))
 }
 
 fn normalize_loader(loader: &str) -> AppResult<String> {
@@ -155,6 +183,9 @@ async fn build_summary(app: &AppHandle, instance: &Instance) -> InstanceSummary
         created_at: instance.created_at,
         last_played: instance.last_played,
         mods_count,
+        settings: instance.settings.clone(),
+        shared_folders: instance.shared_folders.clone(),
+        allow_multiple: instance.allow_multiple,
     }
 }
 
@@ -163,13 +194,19 @@ pub async fn list_instances_impl(app: &AppHandle) -> AppResult<Vec<InstanceSumma
     let mut out = Vec::new();
     for inst in instances.iter() {
         out.push(build_summary(app, inst).await);
+    }
+    // Un solo refresco en segundo plano a la vez, sin importar cuantas veces se liste.
+    if app.state::<AppState>().instances.begin_mods_refresh() {
         let app_handle = app.clone();
-        let inst_id = inst.id.clone();
+        let ids: Vec<String> = instances.iter().map(|i| i.id.clone()).collect();
         tokio::spawn(async move {
-            refresh_mods_cache(&app_handle, &inst_id).await;
+            for id in ids {
+                refresh_mods_cache(&app_handle, &id).await;
+            }
+            app_handle.state::<AppState>().instances.end_mods_refresh();
         });
     }
-    out.sort_by(|a, b| b.last_played.unwrap_or(0).cmp(&a.last_played.unwrap_or(0)));
+    out.sort_by_key(|inst| std::cmp::Reverse(inst.last_played.unwrap_or(0)));
     Ok(out)
 }
 
@@ -206,7 +243,6 @@ pub async fn create_instance_impl(
         return Err("La versión no está instalada".to_string().into());
     }
 
-    let mut instances = load_instances(app).await?;
     let id = Uuid::new_v4().to_string();
     let instance = Instance {
         id: id.clone(),
@@ -218,9 +254,15 @@ pub async fn create_instance_impl(
         created_at: now_millis(),
         last_played: None,
         mods_cached_count: Some(0),
+        settings: None,
+        shared_folders: Vec::new(),
+        allow_multiple: false,
     };
-    instances.push(instance.clone());
-    save_instances(app, &instances).await?;
+    update_instances(app, |instances| {
+        instances.push(instance.clone());
+        Ok(())
+    })
+    .await?;
 
     let dir = instance_dir(app, &id);
     tokio_fs::create_dir_all(dir.join("mods"))
@@ -240,46 +282,221 @@ pub async fn update_instance_impl(
     instance_id: String,
     req: InstanceUpdateRequest,
 ) -> AppResult<InstanceSummary> {
-    let mut instances = load_instances(app).await?;
-    let mut found = None;
-    for inst in &mut instances {
-        if inst.id == instance_id {
-            if let Some(name) = req.name.as_ref() {
-                if !name.trim().is_empty() {
-                    inst.name = name.trim().to_string();
-                }
-            }
-            if let Some(thumbnail) = req.thumbnail {
-                inst.thumbnail = if thumbnail.trim().is_empty() { None } else { Some(thumbnail) };
-            }
-            if let Some(tags) = req.tags {
-                inst.tags = tags;
-            }
-            if inst.mods_cached_count.is_none() {
-                inst.mods_cached_count = load_cached_mods_count(app, &instance_id).await;
+    let settings = match req.settings {
+        Some(settings) => Some(normalize_instance_settings(settings)?),
+        None => None,
+    };
+    let cached_count = load_cached_mods_count(app, &instance_id).await;
+    let instance = update_instances(app, |instances| {
+        let inst = instances.iter_mut().find(|i| i.id == instance_id).ok_or_else(|| {
+            crate::error::AppError::Message("Instancia no encontrada".to_string())
+        })?;
+        if let Some(name) = req.name.as_ref() {
+            if !name.trim().is_empty() {
+                inst.name = name.trim().to_string();
             }
-            found = Some(inst.clone());
-            break;
+        }
+        if let Some(thumbnail) = req.thumbnail {
+            inst.thumbnail = if thumbnail.trim().is_empty() { None } else { Some(thumbnail) };
+        }
+        if let Some(tags) = req.tags {
+            inst.tags = tags;
+        }
+        if let Some(settings) = settings {
+            inst.settings = settings;
+        }
+        if inst.mods_cached_count.is_none() {
+            inst.mods_cached_count = cached_count;
+        }
+        Ok(inst.clone())
+    })
+    .await?;
+    Ok(build_summary(app, &instance).await)
+}
+
+// Carpetas con archivos grandes que no se editan en sitio: se enlazan en vez de copiarse.
+const LINKED_CONTENT_DIRS: [&str; 3] = ["mods", "resourcepacks", "shaderpacks"];
+
+#[derive(Default)]
+struct DuplicateStats {
+    linked: u64,
+    copied: u64,
+}
+
+// Estado propio de la instancia original en `.launcher/`: historial de juego, bisect en curso,
+// respaldos y carpetas de trabajo. La metadata de mods y los sets si se copian.
+const INSTANCE_STATE_ENTRIES: [&str; 5] =
+    ["sessions.json", "bisect.json", "replaced", "upgrade", "import"];
+
+fn should_skip_duplicate(rel: &Path, include_saves: bool) -> bool {
+    let mut components = rel.components().map(|c| c.as_os_str().to_string_lossy().to_string());
+    let first = match components.next() {
+        Some(first) => first,
+        None => return true,
+    };
+    if first == "saves" {
+        return !include_saves;
+    }
+    if first == ".launcher" {
+        return components
+            .next()
+            .map(|second| INSTANCE_STATE_ENTRIES.contains(&second.as_str()))
+            .unwrap_or(false);
+    }
+    matches!(first.as_str(), "logs" | "crash-reports" | "mods.cache.json")
+}
+
+fn link_or_copy_file(src: &Path, dest: &Path, stats: &mut DuplicateStats) -> AppResult<()> {
+    if std::fs::hard_link(src, dest).is_ok() {
+        stats.linked += 1;
+        return Ok(());
+    }
+    std::fs::copy(src, dest).map_err(|e| crate::error::AppError::Message(e.to_string()))?;
+    stats.copied += 1;
+    Ok(())
+}
+
+fn duplicate_dir(
+    base: &Path,
+    dir: &Path,
+    dest_base: &Path,
+    include_saves: bool,
+    stats: &mut DuplicateStats,
+) -> AppResult<()> {
+    let entries =
+        std::fs::read_dir(dir).map_err(|e| crate::error::AppError::Message(e.to_string()))?;
+    for entry in entries {
+        let entry = entry.map_err(|e| crate::error::AppError::Message(e.to_string()))?;
+        let path = entry.path();
+        let rel =
+            path.strip_prefix(base).map_err(|e| crate::error::AppError::Message(e.to_string()))?;
+        if should_skip_duplicate(rel, include_saves) {
+            continue;
+        }
+        let dest = dest_base.join(rel);
+        let ft = entry.file_type().map_err(|e| crate::error::AppError::Message(e.to_string()))?;
+        if ft.is_dir() {
+            std::fs::create_dir_all(&dest)
+                .map_err(|e| crate::error::AppError::Message(e.to_string()))?;
+            duplicate_dir(base, &path, dest_base, include_saves, stats)?;
+            continue;
+        }
+        if !ft.is_file() {
+            continue;
+        }
+        let linkable = rel
+            .components()
+            .next()
+            .map(|c| LINKED_CONTENT_DIRS.iter().any(|d| c.as_os_str() == *d))
+            .unwrap_or(false);
+        if linkable {
+            link_or_copy_file(&path, &dest, stats)?;
+        } else {
+            std::fs::copy(&path, &dest)
+                .map_err(|e| crate::error::AppError::Message(e.to_string()))?;
+            stats.copied += 1;
         }
     }
-    let instance = found
-        .ok_or_else(|| crate::error::AppError::Message("Instancia no encontrada".to_string()))?;
-    save_instances(app, &instances).await?;
+    Ok(())
+}
+
+pub async fn duplicate_instance_impl(
+    app: &AppHandle,
+    instance_id: String,
+    req: InstanceDuplicateRequest,
+) -> AppResult<InstanceSummary> {
+    let instances = load_instances(app).await?;
+    let source =
+        instances.iter().find(|i| i.id == instance_id).cloned().ok_or_else(|| {
+            crate::error::AppError::Message("Instancia no encontrada".to_string())
+        })?;
+    let src_dir = instance_dir(app, &instance_id);
+    if !src_dir.exists() {
+        return Err("La carpeta de la instancia no existe".to_string().into());
+    }
+
+    let name = req
+        .name
+        .map(|n| n.trim().to_string())
+        .filter(|n| !n.is_empty())
+        .unwrap_or_else(|| format!("{} (copia)", source.name));
+    let id = Uuid::new_v4().to_string();
+    let dest_dir = instance_dir(app, &id);
+    let include_saves = req.include_saves;
+
+    let src_clone = src_dir.clone();
+    let dest_clone = dest_dir.clone();
+    let copied = tokio::task::spawn_blocking(move || -> AppResult<DuplicateStats> {
+        let mut stats = DuplicateStats::default();
+        std::fs::create_dir_all(&dest_clone)
+            .map_err(|e| crate::error::AppError::Message(e.to_string()))?;
+        duplicate_dir(&src_clone, &src_clone, &dest_clone, include_saves, &mut stats)?;
+        Ok(stats)
+    })
+    .await
+    .map_err(|e| crate::error::AppError::Message(e.to_string()));
+    let stats = match copied {
+        Ok(Ok(stats)) => stats,
+        Ok(Err(e)) | Err(e) => {
+            let _ = tokio_fs::remove_dir_all(&dest_dir).await;
+            return Err(e);
+        }
+    };
+
+    let instance =
+        Instance { id: id.clone(), name, created_at: now_millis(), last_played: None, ..source };
+    let saved = update_instances(app, |instances| {
+        instances.push(instance.clone());
+        Ok(())
+    })
+    .await;
+    if let Err(e) = saved {
+        let _ = tokio_fs::remove_dir_all(&dest_dir).await;
+        return Err(e);
+    }
+    // Las carpetas compartidas no se copian; se vuelven a enlazar en la copia.
+    let _ = repair_shared_folders(app, &id).await;
+    refresh_mods_cache(app, &id).await;
+    let _ = crate::utils::append_action_log(
+        app,
+        &format!(
+            "instance_duplicate source={} instance={} linked={} copied={} saves={}",
+            instance_id, id, stats.linked, stats.copied, include_saves
+        ),
+    )
+    .await;
     Ok(build_summary(app, &instance).await)
 }
 
 pub async fn delete_instance_impl(app: &AppHandle, instance_id: String) -> AppResult<()> {
-    let _ =
-        crate::utils::append_action_log(app, &format!("instance_delete instance={}", instance_id))
-            .await;
-    let mut instances = load_instances(app).await?;
-    let before = instances.len();
-    instances.retain(|i| i.id != instance_id);
-    if instances.len() == before {
-        return Err("Instancia no encontrada".to_string().into());
+    let instance = get_instance_impl(app, &instance_id).await?;
+    let trashed = trash::move_to_trash(app, &instance).await?;
+    let removed = update_instances(app, |instances| {
+        instances.retain(|i| i.id != instance_id);
+        Ok(())
+    })
+    .await;
+    if let Err(e) = removed {
+        let _ = trash::restore_trashed_instance_files(app, &trashed).await;
+        return Err(e);
     }
-    save_instances(app, &instances).await?;
-    let dir = instance_dir(app, &instance_id);
+    let _ = crate::utils::append_action_log(
+        app,
+        &format!("instance_delete instance={} trash={}", instance_id, trashed.trash_id),
+    )
+    .await;
+    trash::purge_expired(app, Some(&trashed.trash_id)).await;
+    Ok(())
+}
+
+/// Borra la instancia sin pasar por la papelera (limpieza de importaciones fallidas).
+pub(crate) async fn discard_instance(app: &AppHandle, instance_id: &str) -> AppResult<()> {
+    update_instances(app, |instances| {
+        instances.retain(|i| i.id != instance_id);
+        Ok(())
+    })
+    .await?;
+    let dir = instance_dir(app, instance_id);
     if dir.exists() {
         tokio_fs::remove_dir_all(dir)
             .await
@@ -289,19 +506,30 @@ pub async fn delete_instance_impl(app: &AppHandle, instance_id: String) -> AppRe
 }
 
 pub async fn touch_instance_impl(app: &AppHandle, instance_id: &str) -> AppResult<()> {
-    let mut instances = load_instances(app).await?;
-    let mut changed = false;
-    for inst in &mut instances {
-        if inst.id == instance_id {
+    update_instances(app, |instances| {
+        if let Some(inst) = instances.iter_mut().find(|i| i.id == instance_id) {
             inst.last_played = Some(now_millis());
-            changed = true;
-            break;
         }
-    }
-    if changed {
-        save_instances(app, &instances).await?;
-    }
-    Ok(())
+        Ok(())
+    })
+    .await
+}
+
+/// Permite o bloquea lanzar la instancia mientras ya hay un juego suyo en ejecucion.
+pub async fn set_instance_allow_multiple_impl(
+    app: &AppHandle,
+    instance_id: &str,
+    allow: bool,
+) -> AppResult<InstanceSummary> {
+    let instance = update_instances(app, |instances| {
+        let inst = instances.iter_mut().find(|i| i.id == instance_id).ok_or_else(|| {
+            crate::error::AppError::Message("Instancia no encontrada".to_string())
+        })?;
+        inst.allow_multiple = allow;
+        Ok(inst.clone())
+    })
+    .await?;
+    Ok(build_summary(app, &instance).await)
 }
 
 pub fn open_instance_folder_impl(app: &AppHandle, instance_id: String) -> AppResult<()> {
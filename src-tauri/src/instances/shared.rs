@@ -0,0 +1,205 @@
+use crate::error::{AppError, AppResult};
+use crate::models::InstanceSummary;
+use crate::utils::{append_action_log, copy_dir_all, get_launcher_dir};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+use super::{build_summary, get_instance_impl, instance_dir, update_instances};
+
+pub(crate) const SHAREABLE_FOLDERS: [&str; 4] =
+    ["screenshots", "resourcepacks", "shaderpacks", "saves"];
+
+fn shared_root(app: &AppHandle) -> PathBuf {
+    get_launcher_dir(app).join("shared")
+}
+
+/// True si `path` es un enlace (symlink o junction) en lugar de una carpeta real.
+pub(crate) fn is_dir_link(path: &Path) -> bool {
+    fs::symlink_metadata(path).map(|m| m.file_type().is_symlink()).unwrap_or(false)
+}
+
+#[cfg(unix)]
+fn create_dir_link(target: &Path, link: &Path) -> AppResult<()> {
+    std::os::unix::fs::symlink(target, link).map_err(|e| AppError::Message(e.to_string()))
+}
+
+// Las junctions no necesitan permisos de administrador ni modo desarrollador.
+#[cfg(windows)]
+fn create_dir_link(target: &Path, link: &Path) -> AppResult<()> {
+    let mut cmd = std::process::Command::new("cmd");
+    cmd.arg("/C").arg("mklink").arg("/J").arg(link).arg(target);
+    crate::utils::hide_background_window(&mut cmd);
+    let output = cmd.output().map_err(|e| AppError::Message(e.to_string()))?;
+    if !output.status.success() {
+        return Err(format!(
+            "No se pudo crear el enlace: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )
+        .into());
+    }
+    Ok(())
+}
+
+fn remove_dir_link(link: &Path) -> AppResult<()> {
+    // En Windows una junction se borra como carpeta; en Unix el symlink es un archivo.
+    fs::remove_file(link)
+        .or_else(|_| fs::remove_dir(link))
+        .map_err(|e| AppError::Message(e.to_string()))
+}
+
+// Mueve el contenido de una carpeta local al destino compartido. Lo que ya existe en el
+// destino se aparta en `conflicts` para no pisar archivos de otras instancias.
+fn merge_into_shared(src: &Path, target: &Path, conflicts: &Path) -> AppResult<()> {
+    for entry in fs::read_dir(src).map_err(|e| AppError::Message(e.to_string()))? {
+        let entry = entry.map_err(|e| AppError::Message(e.to_string()))?;
+        let dest = target.join(entry.file_name());
+        let dest = if dest.exists() {
+            fs::create_dir_all(conflicts).map_err(|e| AppError::Message(e.to_string()))?;
+            conflicts.join(entry.file_name())
+        } else {
+            dest
+        };
+        fs::rename(entry.path(), &dest).map_err(|e| AppError::Message(e.to_string()))?;
+    }
+    Ok(())
+}
+
+/// Deja `instance/<folder>` como enlace a `shared/<folder>`. Devuelve true si hubo que tocar algo.
+fn ensure_shared_link(shared_base: &Path, inst_dir: &Path, folder: &str) -> AppResult<bool> {
+    let target = shared_base.join(folder);
+    fs::create_dir_all(&target).map_err(|e| AppError::Message(e.to_string()))?;
+    let link = inst_dir.join(folder);
+
+    if is_dir_link(&link) {
+        let points_to_target = match (fs::canonicalize(&link), fs::canonicalize(&target)) {
+            (Ok(a), Ok(b)) => a == b,
+            _ => false,
+        };
+        if points_to_target {
+            return Ok(false);
+        }
+        // Enlace roto o apuntando a otro sitio.
+        remove_dir_link(&link)?;
+    } else if link.is_dir() {
+        let conflicts = inst_dir.join(".launcher").join("shared-conflicts").join(folder);
+        merge_into_shared(&link, &target, &conflicts)?;
+        fs::remove_dir(&link).map_err(|e| AppError::Message(e.to_string()))?;
+    } else if link.exists() {
+        return Err(format!("{} existe pero no es una carpeta", folder).into());
+    }
+    create_dir_link(&target, &link)?;
+    Ok(true)
+}
+
+// Quita el enlace y deja una copia local del contenido compartido.
+fn detach_shared_link(shared_base: &Path, inst_dir: &Path, folder: &str) -> AppResult<()> {
+    let link = inst_dir.join(folder);
+    if !is_dir_link(&link) {
+        return Ok(());
+    }
+    remove_dir_link(&link)?;
+    let target = shared_base.join(folder);
+    if target.is_dir() {
+        copy_dir_all(&target, &link)?;
+    } else {
+        fs::create_dir_all(&link).map_err(|e| AppError::Message(e.to_string()))?;
+    }
+    Ok(())
+}
+
+fn normalize_shared_folders(folders: Vec<String>) -> AppResult<Vec<String>> {
+    let mut out: Vec<String> = Vec::new();
+    for folder in folders {
+        let folder = folder.trim().to_lowercase();
+        if !SHAREABLE_FOLDERS.contains(&folder.as_str()) {
+            return Err(format!(
+                "Carpeta no compartible: {} (usa {})",
+                folder,
+                SHAREABLE_FOLDERS.join(", ")
+            )
+            .into());
+        }
+        if !out.contains(&folder) {
+            out.push(folder);
+        }
+    }
+    Ok(out)
+}
+
+pub async fn set_instance_shared_folders_impl(
+    app: &AppHandle,
+    instance_id: String,
+    folders: Vec<String>,
+) -> AppResult<InstanceSummary> {
+    let folders = normalize_shared_folders(folders)?;
+    let instance = get_instance_impl(app, &instance_id).await?;
+    let inst_dir = instance_dir(app, &instance_id);
+    if !inst_dir.exists() {
+        return Err("La instancia no existe".to_string().into());
+    }
+
+    let removed: Vec<String> =
+        instance.shared_folders.iter().filter(|f| !folders.contains(f)).cloned().collect();
+    let shared_base = shared_root(app);
+    let wanted = folders.clone();
+    let dir = inst_dir.clone();
+    tokio::task::spawn_blocking(move || -> AppResult<()> {
+        for folder in removed.iter() {
+            detach_shared_link(&shared_base, &dir, folder)?;
+        }
+        for folder in wanted.iter() {
+            ensure_shared_link(&shared_base, &dir, folder)?;
+        }
+        Ok(())
+    })
+    .await
+    .map_err(|e| AppError::Message(e.to_string()))??;
+
+    let saved_folders = folders.clone();
+    let instance = update_instances(app, |instances| {
+        let inst = instances
+            .iter_mut()
+            .find(|i| i.id == instance_id)
+            .ok_or_else(|| AppError::Message("Instancia no encontrada".to_string()))?;
+        inst.shared_folders = saved_folders;
+        Ok(inst.clone())
+    })
+    .await?;
+    let _ = append_action_log(
+        app,
+        &format!("instance_shared instance={} folders={}", instance_id, folders.join(",")),
+    )
+    .await;
+    Ok(build_summary(app, &instance).await)
+}
+
+/// Recrea los enlaces compartidos rotos o ausentes de la instancia. Devuelve cuantos reparo.
+pub(crate) async fn repair_shared_folders(app: &AppHandle, instance_id: &str) -> AppResult<u32> {
+    let instance = get_instance_impl(app, instance_id).await?;
+    if instance.shared_folders.is_empty() {
+        return Ok(0);
+    }
+    let shared_base = shared_root(app);
+    let dir = instance_dir(app, instance_id);
+    let folders = instance.shared_folders.clone();
+    let repaired = tokio::task::spawn_blocking(move || -> AppResult<u32> {
+        let mut repaired = 0u32;
+        for folder in folders.iter() {
+            if ensure_shared_link(&shared_base, &dir, folder)? {
+                repaired += 1;
+            }
+        }
+        Ok(repaired)
+    })
+    .await
+    .map_err(|e| AppError::Message(e.to_string()))??;
+    if repaired > 0 {
+        let _ = append_action_log(
+            app,
+            &format!("instance_shared_repair instance={} repaired={}", instance_id, repaired),
+        )
+        .await;
+    }
+    Ok(repaired)
+}
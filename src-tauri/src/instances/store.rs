@@ -0,0 +1,198 @@
+use crate::error::{AppError, AppResult};
+use crate::models::Instance;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant, SystemTime};
+use tokio::fs as tokio_fs;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+pub(crate) const INSTANCES_SCHEMA_VERSION: u32 = 2;
+
+const LOCK_TIMEOUT: Duration = Duration::from_secs(10);
+const LOCK_STALE_AFTER: Duration = Duration::from_secs(30);
+
+#[derive(Serialize, Deserialize)]
+struct InstancesFile {
+    schema_version: u32,
+    instances: Vec<Instance>,
+}
+
+// Cada paso recibe una instancia en el esquema `from` y la deja en `from + 1`.
+fn migrate_instance(from: u32, item: &mut Value) {
+    let obj = match item.as_object_mut() {
+        Some(obj) => obj,
+        None => return,
+    };
+    // v1 -> v2: archivo envuelto con schema_version; las etiquetas pasan a ser obligatorias.
+    if from == 1 {
+        obj.entry("tags").or_insert_with(|| Value::Array(Vec::new()));
+    }
+}
+
+fn migrate_instances(value: Value) -> AppResult<(Vec<Instance>, bool)> {
+    let (mut version, mut items) = match value {
+        Value::Array(items) => (1, items),
+        Value::Object(mut obj) => {
+            let version = obj.get("schema_version").and_then(|v| v.as_u64()).unwrap_or(1) as u32;
+            let items = match obj.remove("instances") {
+                Some(Value::Array(items)) => items,
+                _ => Vec::new(),
+            };
+            (version, items)
+        }
+        _ => return Err("instances.json invalido: formato desconocido".to_string().into()),
+    };
+    if version > INSTANCES_SCHEMA_VERSION {
+        return Err(format!(
+            "instances.json usa un esquema mas nuevo ({}). Actualiza el launcher.",
+            version
+        )
+        .into());
+    }
+    let migrated = version < INSTANCES_SCHEMA_VERSION;
+    while version < INSTANCES_SCHEMA_VERSION {
+        for item in items.iter_mut() {
+            migrate_instance(version, item);
+        }
+        version += 1;
+    }
+    let instances = items
+        .into_iter()
+        .map(serde_json::from_value::<Instance>)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| AppError::Message(format!("instances.json invalido: {}", e)))?;
+    Ok((instances, migrated))
+}
+
+pub(crate) fn parse_instances_file(raw: &str) -> AppResult<(Vec<Instance>, bool)> {
+    let trimmed = raw.trim_start_matches('\u{feff}').trim();
+    if trimmed.is_empty() {
+        return Ok((Vec::new(), false));
+    }
+    let value: Value = serde_json::from_str(trimmed)
+        .map_err(|e| AppError::Message(format!("instances.json invalido: {}", e)))?;
+    migrate_instances(value)
+}
+
+struct InstancesFileLock {
+    path: PathBuf,
+}
+
+impl InstancesFileLock {
+    async fn acquire(path: PathBuf) -> AppResult<Self> {
+        let deadline = Instant::now() + LOCK_TIMEOUT;
+        loop {
+            match tokio_fs::OpenOptions::new().write(true).create_new(true).open(&path).await {
+                Ok(mut file) => {
+                    let _ = file.write_all(std::process::id().to_string().as_bytes()).await;
+                    return Ok(Self { path });
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                    if lock_is_stale(&path).await {
+                        let _ = tokio_fs::remove_file(&path).await;
+                        continue;
+                    }
+                    if Instant::now() >= deadline {
+                        return Err("instances.json esta bloqueado por otro proceso"
+                            .to_string()
+                            .into());
+                    }
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                }
+                Err(e) => return Err(AppError::Message(e.to_string())),
+            }
+        }
+    }
+}
+
+impl Drop for InstancesFileLock {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+async fn lock_is_stale(path: &Path) -> bool {
+    let modified = match tokio_fs::metadata(path).await.and_then(|m| m.modified()) {
+        Ok(m) => m,
+        Err(_) => return false,
+    };
+    SystemTime::now().duration_since(modified).map(|age| age > LOCK_STALE_AFTER).unwrap_or(false)
+}
+
+async fn read_from_disk(path: &Path) -> AppResult<(Vec<Instance>, bool)> {
+    if !tokio_fs::try_exists(path).await.unwrap_or(false) {
+        return Ok((Vec::new(), false));
+    }
+    let raw = tokio_fs::read_to_string(path).await.map_err(|e| AppError::Message(e.to_string()))?;
+    parse_instances_file(&raw)
+}
+
+async fn write_to_disk(path: &Path, instances: &[Instance]) -> AppResult<()> {
+    if let Some(parent) = path.parent() {
+        tokio_fs::create_dir_all(parent).await.map_err(|e| AppError::Message(e.to_string()))?;
+    }
+    let payload =
+        InstancesFile { schema_version: INSTANCES_SCHEMA_VERSION, instances: instances.to_vec() };
+    let json =
+        serde_json::to_string_pretty(&payload).map_err(|e| AppError::Message(e.to_string()))?;
+    let tmp = path.with_extension("tmp");
+    tokio_fs::write(&tmp, json).await.map_err(|e| AppError::Message(e.to_string()))?;
+    tokio_fs::rename(&tmp, path).await.map_err(|e| AppError::Message(e.to_string()))?;
+    Ok(())
+}
+
+fn lock_path(path: &Path) -> PathBuf {
+    path.with_extension("json.lock")
+}
+
+/// Unico escritor de instances.json: cache en memoria, escrituras serializadas y lock de archivo
+/// para que otro proceso del launcher no pise los cambios.
+#[derive(Default)]
+pub struct InstanceStore {
+    cache: Mutex<Option<Vec<Instance>>>,
+    mods_refresh_running: AtomicBool,
+}
+
+impl InstanceStore {
+    pub async fn load(&self, path: &Path) -> AppResult<Vec<Instance>> {
+        let mut cache = self.cache.lock().await;
+        if let Some(list) = cache.as_ref() {
+            return Ok(list.clone());
+        }
+        let _lock = InstancesFileLock::acquire(lock_path(path)).await?;
+        let (instances, migrated) = read_from_disk(path).await?;
+        if migrated {
+            let backup = path.with_extension("json.bak");
+            let _ = tokio_fs::copy(path, &backup).await;
+            write_to_disk(path, &instances).await?;
+        }
+        *cache = Some(instances.clone());
+        Ok(instances)
+    }
+
+    /// Relee el archivo bajo lock, aplica `f` y persiste el resultado antes de soltar el lock.
+    pub async fn update<T, F>(&self, path: &Path, f: F) -> AppResult<T>
+    where
+        F: FnOnce(&mut Vec<Instance>) -> AppResult<T>,
+    {
+        let mut cache = self.cache.lock().await;
+        let _lock = InstancesFileLock::acquire(lock_path(path)).await?;
+        let (mut instances, _) = read_from_disk(path).await?;
+        let out = f(&mut instances)?;
+        write_to_disk(path, &instances).await?;
+        *cache = Some(instances);
+        Ok(out)
+    }
+
+    /// Devuelve false si ya hay un refresco de conteo de mods en curso.
+    pub fn begin_mods_refresh(&self) -> bool {
+        !self.mods_refresh_running.swap(true, Ordering::SeqCst)
+    }
+
+    pub fn end_mods_refresh(&self) {
+        self.mods_refresh_running.store(false, Ordering::SeqCst);
+    }
+}
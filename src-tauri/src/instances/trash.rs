@@ -0,0 +1,293 @@
+use crate::error::{AppError, AppResult};
+use crate::models::{Instance, InstanceSummary, TrashConfig, TrashedInstance};
+use crate::utils::{append_action_log, get_launcher_dir};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+use tokio::fs as tokio_fs;
+
+use super::{build_summary, instance_dir, load_instances, now_millis, update_instances};
+
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+fn trash_root(app: &AppHandle) -> PathBuf {
+    get_launcher_dir(app).join("trash")
+}
+
+fn config_path(app: &AppHandle) -> PathBuf {
+    trash_root(app).join("config.json")
+}
+
+fn entry_dir(app: &AppHandle, trash_id: &str) -> AppResult<PathBuf> {
+    let valid = !trash_id.is_empty()
+        && trash_id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err("Identificador de papelera invalido".to_string().into());
+    }
+    Ok(trash_root(app).join(trash_id))
+}
+
+// Cada entrada guarda la carpeta original en `instance/` y el registro en `trash.json`.
+fn entry_data_dir(entry: &Path) -> PathBuf {
+    entry.join("instance")
+}
+
+fn entry_meta_path(entry: &Path) -> PathBuf {
+    entry.join("trash.json")
+}
+
+fn dir_size(path: &Path) -> u64 {
+    let entries = match fs::read_dir(path) {
+        Ok(entries) => entries,
+        Err(_) => return 0,
+    };
+    let mut total = 0u64;
+    for entry in entries.flatten() {
+        let ft = match entry.file_type() {
+            Ok(ft) => ft,
+            Err(_) => continue,
+        };
+        if ft.is_dir() {
+            total += dir_size(&entry.path());
+        } else if ft.is_file() {
+            total += entry.metadata().map(|m| m.len()).unwrap_or(0);
+        }
+    }
+    total
+}
+
+async fn write_entry_meta(entry: &Path, meta: &TrashedInstance) -> AppResult<()> {
+    let raw = serde_json::to_string_pretty(meta).map_err(|e| AppError::Message(e.to_string()))?;
+    tokio_fs::write(entry_meta_path(entry), raw).await.map_err(|e| AppError::Message(e.to_string()))
+}
+
+async fn read_entry_meta(entry: &Path) -> Option<TrashedInstance> {
+    let raw = tokio_fs::read_to_string(entry_meta_path(entry)).await.ok()?;
+    serde_json::from_str(raw.trim_start_matches('\u{feff}')).ok()
+}
+
+async fn read_all_entries(app: &AppHandle) -> Vec<TrashedInstance> {
+    let mut out = Vec::new();
+    let mut rd = match tokio_fs::read_dir(trash_root(app)).await {
+        Ok(rd) => rd,
+        Err(_) => return out,
+    };
+    while let Ok(Some(entry)) = rd.next_entry().await {
+        if !entry.file_type().await.map(|ft| ft.is_dir()).unwrap_or(false) {
+            continue;
+        }
+        if let Some(meta) = read_entry_meta(&entry.path()).await {
+            out.push(meta);
+        }
+    }
+    out.sort_by_key(|entry| std::cmp::Reverse(entry.deleted_at));
+    out
+}
+
+async fn remove_entry(app: &AppHandle, trash_id: &str) -> AppResult<()> {
+    let entry = entry_dir(app, trash_id)?;
+    if tokio_fs::try_exists(&entry).await.unwrap_or(false) {
+        tokio_fs::remove_dir_all(&entry).await.map_err(|e| AppError::Message(e.to_string()))?;
+    }
+    Ok(())
+}
+
+pub async fn get_trash_config_impl(app: &AppHandle) -> AppResult<TrashConfig> {
+    match tokio_fs::read_to_string(config_path(app)).await {
+        Ok(raw) => Ok(serde_json::from_str(raw.trim_start_matches('\u{feff}')).unwrap_or_default()),
+        Err(_) => Ok(TrashConfig::default()),
+    }
+}
+
+pub async fn set_trash_config_impl(app: &AppHandle, config: TrashConfig) -> AppResult<TrashConfig> {
+    let config = TrashConfig {
+        max_age_days: config.max_age_days.filter(|d| *d > 0),
+        max_size_mb: config.max_size_mb.filter(|s| *s > 0),
+    };
+    tokio_fs::create_dir_all(trash_root(app))
+        .await
+        .map_err(|e| AppError::Message(e.to_string()))?;
+    let raw =
+        serde_json::to_string_pretty(&config).map_err(|e| AppError::Message(e.to_string()))?;
+    tokio_fs::write(config_path(app), raw).await.map_err(|e| AppError::Message(e.to_string()))?;
+    purge_expired(app, None).await;
+    Ok(config)
+}
+
+async fn purge_entry(app: &AppHandle, entry: &TrashedInstance) -> bool {
+    if remove_entry(app, &entry.trash_id).await.is_err() {
+        return false;
+    }
+    let _ = append_action_log(
+        app,
+        &format!("trash_auto_purge trash={} instance={}", entry.trash_id, entry.instance.id),
+    )
+    .await;
+    true
+}
+
+/// Aplica los limites de antiguedad y tamano total; las entradas mas viejas se borran primero.
+/// `keep` (la instancia recien borrada) nunca se purga, aunque sola supere el limite.
+pub(crate) async fn purge_expired(app: &AppHandle, keep: Option<&str>) -> u32 {
+    let config = get_trash_config_impl(app).await.unwrap_or_default();
+    let now = now_millis();
+    let max_bytes = config.max_size_mb.map(|mb| mb.saturating_mul(1024 * 1024));
+
+    let mut purged = 0u32;
+    let mut kept: Vec<TrashedInstance> = Vec::new();
+    for entry in read_all_entries(app).await {
+        let expired = config
+            .max_age_days
+            .map(|days| now - entry.deleted_at > days as i64 * DAY_MS)
+            .unwrap_or(false);
+        if expired && keep != Some(entry.trash_id.as_str()) && purge_entry(app, &entry).await {
+            purged += 1;
+            continue;
+        }
+        kept.push(entry);
+    }
+
+    if let Some(max) = max_bytes {
+        let mut total: u64 = kept.iter().map(|e| e.size_bytes).sum();
+        // `kept` viene de mas nuevo a mas viejo: se recorre desde el final.
+        for entry in kept.iter().rev() {
+            if total <= max {
+                break;
+            }
+            if keep == Some(entry.trash_id.as_str()) {
+                continue;
+            }
+            if purge_entry(app, entry).await {
+                purged += 1;
+                total = total.saturating_sub(entry.size_bytes);
+            }
+        }
+    }
+    purged
+}
+
+/// Mueve la carpeta de la instancia a la papelera. Se llama antes de quitarla de instances.json;
+/// si eso falla, `restore_trashed_instance_files` la devuelve a su lugar.
+pub(crate) async fn move_to_trash(
+    app: &AppHandle,
+    instance: &Instance,
+) -> AppResult<TrashedInstance> {
+    let deleted_at = now_millis();
+    let trash_id = format!("{}-{}", instance.id, deleted_at);
+    let entry = entry_dir(app, &trash_id)?;
+    tokio_fs::create_dir_all(&entry).await.map_err(|e| AppError::Message(e.to_string()))?;
+
+    let source = instance_dir(app, &instance.id);
+    let data_dir = entry_data_dir(&entry);
+    if tokio_fs::try_exists(&source).await.unwrap_or(false) {
+        if let Err(e) = tokio_fs::rename(&source, &data_dir).await {
+            let _ = tokio_fs::remove_dir_all(&entry).await;
+            return Err(format!("Mover la instancia a la papelera: {}", e).into());
+        }
+    }
+
+    let size_dir = data_dir.clone();
+    let size_bytes = tokio::task::spawn_blocking(move || dir_size(&size_dir)).await.unwrap_or(0);
+    let meta = TrashedInstance { trash_id, instance: instance.clone(), deleted_at, size_bytes };
+    if let Err(e) = write_entry_meta(&entry, &meta).await {
+        let _ = tokio_fs::rename(&data_dir, &source).await;
+        let _ = tokio_fs::remove_dir_all(&entry).await;
+        return Err(e);
+    }
+    Ok(meta)
+}
+
+// Deshace `move_to_trash` cuando no se pudo quitar la instancia de instances.json.
+pub(crate) async fn restore_trashed_instance_files(
+    app: &AppHandle,
+    meta: &TrashedInstance,
+) -> AppResult<()> {
+    let entry = entry_dir(app, &meta.trash_id)?;
+    let data_dir = entry_data_dir(&entry);
+    if tokio_fs::try_exists(&data_dir).await.unwrap_or(false) {
+        tokio_fs::rename(&data_dir, instance_dir(app, &meta.instance.id))
+            .await
+            .map_err(|e| AppError::Message(e.to_string()))?;
+    }
+    tokio_fs::remove_dir_all(&entry).await.map_err(|e| AppError::Message(e.to_string()))
+}
+
+pub async fn list_trashed_instances_impl(app: &AppHandle) -> AppResult<Vec<TrashedInstance>> {
+    purge_expired(app, None).await;
+    Ok(read_all_entries(app).await)
+}
+
+pub async fn restore_trashed_instance_impl(
+    app: &AppHandle,
+    trash_id: String,
+) -> AppResult<InstanceSummary> {
+    let entry = entry_dir(app, &trash_id)?;
+    let meta = read_entry_meta(&entry)
+        .await
+        .ok_or_else(|| AppError::Message("La entrada de la papelera no existe".to_string()))?;
+    let instance = meta.instance.clone();
+
+    let existing = load_instances(app).await?;
+    if existing.iter().any(|i| i.id == instance.id) {
+        return Err("Ya existe una instancia con el mismo identificador".to_string().into());
+    }
+    let dest = instance_dir(app, &instance.id);
+    if tokio_fs::try_exists(&dest).await.unwrap_or(false) {
+        return Err("La carpeta de la instancia ya existe".to_string().into());
+    }
+
+    let data_dir = entry_data_dir(&entry);
+    if tokio_fs::try_exists(&data_dir).await.unwrap_or(false) {
+        if let Some(parent) = dest.parent() {
+            tokio_fs::create_dir_all(parent).await.map_err(|e| AppError::Message(e.to_string()))?;
+        }
+        tokio_fs::rename(&data_dir, &dest)
+            .await
+            .map_err(|e| AppError::Message(format!("Restaurar la instancia: {}", e)))?;
+    } else {
+        tokio_fs::create_dir_all(dest.join("mods"))
+            .await
+            .map_err(|e| AppError::Message(e.to_string()))?;
+    }
+
+    let saved = update_instances(app, |instances| {
+        instances.push(instance.clone());
+        Ok(())
+    })
+    .await;
+    if let Err(e) = saved {
+        let _ = tokio_fs::rename(&dest, &data_dir).await;
+        return Err(e);
+    }
+    let _ = tokio_fs::remove_dir_all(&entry).await;
+    let _ = append_action_log(
+        app,
+        &format!("trash_restore trash={} instance={}", trash_id, instance.id),
+    )
+    .await;
+    Ok(build_summary(app, &instance).await)
+}
+
+/// Borra definitivamente una entrada, o toda la papelera si no se indica `trash_id`.
+pub async fn purge_trashed_instances_impl(
+    app: &AppHandle,
+    trash_id: Option<String>,
+) -> AppResult<u32> {
+    let ids: Vec<String> = match trash_id {
+        Some(id) => {
+            let entry = entry_dir(app, &id)?;
+            if !tokio_fs::try_exists(&entry).await.unwrap_or(false) {
+                return Err("La entrada de la papelera no existe".to_string().into());
+            }
+            vec![id]
+        }
+        None => read_all_entries(app).await.into_iter().map(|e| e.trash_id).collect(),
+    };
+    let mut purged = 0u32;
+    for id in ids {
+        remove_entry(app, &id).await?;
+        purged += 1;
+        let _ = append_action_log(app, &format!("trash_purge trash={}", id)).await;
+    }
+    Ok(purged)
+}
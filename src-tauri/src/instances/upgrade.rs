@@ -0,0 +1,458 @@
+use crate::content::{
+    load_mods_metadata, metadata_kind_matches, save_mods_metadata, strip_disabled,
+};
+use crate::downloader::{
+    download_client_impl, download_game_files_impl, get_version_metadata_impl,
+};
+use crate::error::{AppError, AppResult};
+use crate::fabric::install_fabric_impl;
+use crate::forge::install_forge_impl;
+use crate::models::{
+    InstanceSummary, InstanceUpgradePreview, InstanceUpgradeRequest, ModMetadataEntry,
+    ModrinthVersion, ProgressPayload, UpgradeModEntry, UpgradeModStatus, VersionManifest,
+    VersionMetadata,
+};
+use crate::modrinth::modrinth_list_versions_impl;
+use crate::neoforge::install_neoforge_impl;
+use crate::utils::append_action_log;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter};
+use tokio::fs as tokio_fs;
+
+use super::{
+    build_summary, get_instance_impl, instance_dir, normalize_loader, refresh_mods_cache,
+    update_instances,
+};
+
+/// Instala (o reutiliza) el loader pedido y devuelve el id de version resultante.
+pub(crate) async fn install_loader(
+    app: &AppHandle,
+    loader: &str,
+    mc_version: String,
+    loader_version: Option<String>,
+    manifest_cache: &Mutex<Option<VersionManifest>>,
+    metadata_cache: &Mutex<Option<VersionMetadata>>,
+) -> AppResult<String> {
+    match loader {
+        "neoforge" => {
+            install_neoforge_impl(app, mc_version, loader_version, manifest_cache, metadata_cache)
+                .await
+                .map_err(|e| AppError::Message(format!("Instalar NeoForge: {}", e)))
+        }
+        "forge" => {
+            install_forge_impl(app, mc_version, loader_version, manifest_cache, metadata_cache)
+                .await
+                .map_err(|e| AppError::Message(format!("Instalar Forge: {}", e)))
+        }
+        "fabric" => {
+            install_fabric_impl(app, mc_version, loader_version, manifest_cache, metadata_cache)
+                .await
+                .map_err(|e| AppError::Message(format!("Instalar Fabric: {}", e)))
+        }
+        _ => {
+            get_version_metadata_impl(app, mc_version.clone(), manifest_cache, metadata_cache)
+                .await
+                .map_err(|e| AppError::Message(format!("Metadata Minecraft: {}", e)))?;
+            download_client_impl(app, mc_version.clone(), metadata_cache)
+                .await
+                .map_err(|e| AppError::Message(format!("Descargar cliente: {}", e)))?;
+            download_game_files_impl(app, mc_version.clone(), metadata_cache)
+                .await
+                .map_err(|e| AppError::Message(format!("Descargar assets: {}", e)))?;
+            Ok(mc_version)
+        }
+    }
+}
+
+fn upgrade_dir(app: &AppHandle, instance_id: &str) -> PathBuf {
+    instance_dir(app, instance_id).join(".launcher").join("upgrade")
+}
+
+fn is_mod_file(name: &str) -> bool {
+    let lower = name.to_lowercase();
+    lower.ends_with(".jar") || lower.ends_with(".jar.disabled")
+}
+
+fn modrinth_loader(loader: &str) -> Option<String> {
+    match loader {
+        "fabric" | "forge" | "neoforge" => Some(loader.to_string()),
+        _ => None,
+    }
+}
+
+struct UpgradePlan {
+    preview: InstanceUpgradePreview,
+    versions: HashMap<String, ModrinthVersion>,
+}
+
+async fn build_plan(
+    app: &AppHandle,
+    instance_id: &str,
+    req: &InstanceUpgradeRequest,
+) -> AppResult<UpgradePlan> {
+    let instance = get_instance_impl(app, instance_id).await?;
+    let mc_version = req.mc_version.trim().to_string();
+    if mc_version.is_empty() {
+        return Err("Indica la version de Minecraft destino".to_string().into());
+    }
+    let loader = match req.loader.as_deref() {
+        Some(l) if !l.trim().is_empty() => normalize_loader(l)?,
+        _ => instance.loader.clone(),
+    };
+    let loader_version =
+        req.loader_version.as_ref().map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+
+    let metadata = load_mods_metadata(app, instance_id).await;
+    let tracked: HashMap<String, &ModMetadataEntry> = metadata
+        .iter()
+        .filter(|m| metadata_kind_matches(&m.kind, "mods"))
+        .filter(|m| m.project_id.is_some())
+        .map(|m| (m.file_name.clone(), m))
+        .collect();
+
+    let mut files = Vec::new();
+    if let Ok(mut rd) = tokio_fs::read_dir(instance_dir(app, instance_id).join("mods")).await {
+        while let Ok(Some(entry)) = rd.next_entry().await {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if entry.file_type().await.map(|ft| ft.is_file()).unwrap_or(false) && is_mod_file(&name)
+            {
+                files.push(name);
+            }
+        }
+    }
+    files.sort();
+
+    let target_loader = modrinth_loader(&loader);
+    let mut mods = Vec::new();
+    let mut versions = HashMap::new();
+    for disk_name in files {
+        let (clean_name, enabled) = strip_disabled(&disk_name);
+        let meta = match tracked.get(&clean_name) {
+            Some(meta) => *meta,
+            None => {
+                mods.push(UpgradeModEntry {
+                    file_name: clean_name,
+                    enabled,
+                    project_id: None,
+                    current_version_id: None,
+                    target_version_id: None,
+                    target_file_name: None,
+                    status: UpgradeModStatus::LocalOnly,
+                });
+                continue;
+            }
+        };
+        if meta.pinned {
+            mods.push(UpgradeModEntry {
+                file_name: clean_name,
+                enabled,
+                project_id: meta.project_id.clone(),
+                current_version_id: meta.version_id.clone(),
+                target_version_id: None,
+                target_file_name: None,
+                status: UpgradeModStatus::Pinned,
+            });
+            continue;
+        }
+        let project_id = match meta.project_id.clone() {
+            Some(id) => id,
+            None => {
+                mods.push(UpgradeModEntry {
+                    file_name: clean_name,
+                    enabled,
+                    project_id: None,
+                    current_version_id: meta.version_id.clone(),
+                    target_version_id: None,
+                    target_file_name: None,
+                    status: UpgradeModStatus::LocalOnly,
+                });
+                continue;
+            }
+        };
+        // Sin loader compatible con Modrinth no hay version que buscar. Un error de red corta
+        // el plan: tomarlo como "sin version" desactivaria mods que si tienen.
+        let candidates = match target_loader.as_ref() {
+            Some(l) => modrinth_list_versions_impl(
+                app,
+                project_id.clone(),
+                Some(l.clone()),
+                Some(mc_version.clone()),
+            )
+            .await
+            .map_err(|e| {
+                AppError::Message(format!(
+                    "No se pudo consultar Modrinth para {}: {}",
+                    clean_name, e
+                ))
+            })?,
+            None => Vec::new(),
+        };
+        let target = candidates.into_iter().find(|v| !v.files.is_empty());
+        let (status, target_version_id, target_file_name) = match target {
+            Some(v) => {
+                let file = v.files.iter().find(|f| f.primary).unwrap_or(&v.files[0]);
+                let file_name = file.filename.clone();
+                let status = if meta.version_id.as_deref() == Some(v.id.as_str()) {
+                    UpgradeModStatus::Unchanged
+                } else {
+                    UpgradeModStatus::Upgrade
+                };
+                let id = v.id.clone();
+                if status == UpgradeModStatus::Upgrade {
+                    versions.insert(clean_name.clone(), v);
+                }
+                (status, Some(id), Some(file_name))
+            }
+            None => (UpgradeModStatus::Unavailable, None, None),
+        };
+        mods.push(UpgradeModEntry {
+            file_name: clean_name,
+            enabled,
+            project_id: Some(project_id),
+            current_version_id: meta.version_id.clone(),
+            target_version_id,
+            target_file_name,
+            status,
+        });
+    }
+
+    let count =
+        |status: UpgradeModStatus| mods.iter().filter(|m| m.status == status).count() as u32;
+    let preview = InstanceUpgradePreview {
+        instance_id: instance_id.to_string(),
+        from_version: instance.version.clone(),
+        from_loader: instance.loader.clone(),
+        mc_version,
+        loader,
+        loader_version,
+        upgrade_count: count(UpgradeModStatus::Upgrade),
+        unavailable_count: count(UpgradeModStatus::Unavailable),
+        local_only_count: count(UpgradeModStatus::LocalOnly),
+        pinned_count: count(UpgradeModStatus::Pinned),
+        mods,
+    };
+    Ok(UpgradePlan { preview, versions })
+}
+
+pub async fn preview_instance_upgrade_impl(
+    app: &AppHandle,
+    instance_id: String,
+    req: InstanceUpgradeRequest,
+) -> AppResult<InstanceUpgradePreview> {
+    Ok(build_plan(app, &instance_id, &req).await?.preview)
+}
+
+// Cada movimiento queda registrado como (origen, destino) para poder deshacerlo.
+type SwapOps = Vec<(PathBuf, PathBuf)>;
+
+fn rollback_swap(ops: SwapOps) {
+    for (from, to) in ops.into_iter().rev() {
+        let _ = fs::rename(&to, &from);
+    }
+}
+
+fn move_file(from: &Path, to: &Path, ops: &mut SwapOps) -> AppResult<()> {
+    fs::rename(from, to)
+        .map_err(|e| AppError::Message(format!("Mover {}: {}", from.to_string_lossy(), e)))?;
+    ops.push((from.to_path_buf(), to.to_path_buf()));
+    Ok(())
+}
+
+fn disk_name(clean_name: &str, enabled: bool) -> String {
+    if enabled {
+        clean_name.to_string()
+    } else {
+        format!("{}.disabled", clean_name)
+    }
+}
+
+// Primero saca todos los jars viejos a `backup/` y despues mete los nuevos, asi un nombre
+// repetido entre versiones no choca.
+fn apply_swaps(
+    mods_dir: &Path,
+    staging: &Path,
+    backup: &Path,
+    preview: &InstanceUpgradePreview,
+    disable_unavailable: bool,
+    ops: &mut SwapOps,
+) -> AppResult<()> {
+    let upgrades: Vec<&UpgradeModEntry> =
+        preview.mods.iter().filter(|m| m.status == UpgradeModStatus::Upgrade).collect();
+    for entry in upgrades.iter() {
+        let current = disk_name(&entry.file_name, entry.enabled);
+        move_file(&mods_dir.join(&current), &backup.join(&current), ops)?;
+    }
+    for entry in upgrades.iter() {
+        let target = entry.target_file_name.as_deref().unwrap_or(&entry.file_name);
+        let dest = mods_dir.join(disk_name(target, entry.enabled));
+        if dest.exists() {
+            return Err(format!("Ya existe {} en la carpeta de mods", target).into());
+        }
+        move_file(&staging.join(target), &dest, ops)?;
+    }
+    if disable_unavailable {
+        for entry in
+            preview.mods.iter().filter(|m| m.status == UpgradeModStatus::Unavailable && m.enabled)
+        {
+            let to = mods_dir.join(disk_name(&entry.file_name, false));
+            move_file(&mods_dir.join(&entry.file_name), &to, ops)?;
+        }
+    }
+    Ok(())
+}
+
+fn swap_mod_files(
+    mods_dir: &Path,
+    staging: &Path,
+    backup: &Path,
+    preview: &InstanceUpgradePreview,
+    disable_unavailable: bool,
+) -> AppResult<SwapOps> {
+    let mut ops = Vec::new();
+    match apply_swaps(mods_dir, staging, backup, preview, disable_unavailable, &mut ops) {
+        Ok(()) => Ok(ops),
+        Err(e) => {
+            rollback_swap(ops);
+            Err(e)
+        }
+    }
+}
+
+pub async fn apply_instance_upgrade_impl(
+    app: &AppHandle,
+    instance_id: String,
+    req: InstanceUpgradeRequest,
+    manifest_cache: &Mutex<Option<VersionManifest>>,
+    metadata_cache: &Mutex<Option<VersionMetadata>>,
+) -> AppResult<InstanceSummary> {
+    let UpgradePlan { preview, versions } = build_plan(app, &instance_id, &req).await?;
+    if preview.unavailable_count > 0 && !req.disable_unavailable {
+        return Err(format!(
+            "{} mods no tienen version compatible con {}",
+            preview.unavailable_count, preview.mc_version
+        )
+        .into());
+    }
+
+    let _ = app.emit(
+        "download-progress",
+        ProgressPayload { task: "Instalando version destino...".to_string(), percent: 0.0 },
+    );
+    let resolved_version = install_loader(
+        app,
+        &preview.loader,
+        preview.mc_version.clone(),
+        preview.loader_version.clone(),
+        manifest_cache,
+        metadata_cache,
+    )
+    .await?;
+
+    let work_dir = upgrade_dir(app, &instance_id);
+    let staging = work_dir.join("staging");
+    let backup = work_dir.join("backup");
+    let _ = tokio_fs::remove_dir_all(&work_dir).await;
+    tokio_fs::create_dir_all(&staging).await.map_err(|e| AppError::Message(e.to_string()))?;
+    tokio_fs::create_dir_all(&backup).await.map_err(|e| AppError::Message(e.to_string()))?;
+
+    let total = versions.len().max(1) as f64;
+    for (idx, (file_name, version)) in versions.iter().enumerate() {
+        let _ = app.emit(
+            "download-progress",
+            ProgressPayload {
+                task: format!("Descargando {}...", version.name),
+                percent: (idx as f64 / total) * 100.0,
+            },
+        );
+        let file = version.files.iter().find(|f| f.primary).unwrap_or(&version.files[0]);
+        let sha1 = file.hashes.get("sha1").map(|s| s.as_str());
+        let dest = staging.join(&file.filename);
+        if let Err(e) =
+            crate::downloader::download_file_checked(&file.url, &dest, file.size, sha1).await
+        {
+            let _ = tokio_fs::remove_dir_all(&work_dir).await;
+            return Err(format!("Descargar {}: {}", file_name, e).into());
+        }
+    }
+
+    let mods_dir = instance_dir(app, &instance_id).join("mods");
+    let swap_preview = preview.clone();
+    let disable_unavailable = req.disable_unavailable;
+    let (staging_dir, backup_dir) = (staging.clone(), backup.clone());
+    let ops = tokio::task::spawn_blocking(move || {
+        swap_mod_files(&mods_dir, &staging_dir, &backup_dir, &swap_preview, disable_unavailable)
+    })
+    .await
+    .map_err(|e| AppError::Message(e.to_string()))
+    .and_then(|r| r);
+    let ops = match ops {
+        Ok(ops) => ops,
+        Err(e) => {
+            let _ = tokio_fs::remove_dir_all(&work_dir).await;
+            return Err(e);
+        }
+    };
+
+    let new_loader = preview.loader.clone();
+    let new_version = resolved_version.clone();
+    let saved = update_instances(app, |instances| {
+        let inst = instances
+            .iter_mut()
+            .find(|i| i.id == instance_id)
+            .ok_or_else(|| AppError::Message("Instancia no encontrada".to_string()))?;
+        inst.version = new_version;
+        inst.loader = new_loader;
+        Ok(inst.clone())
+    })
+    .await;
+    let instance = match saved {
+        Ok(instance) => instance,
+        Err(e) => {
+            let _ = tokio::task::spawn_blocking(move || rollback_swap(ops)).await;
+            let _ = tokio_fs::remove_dir_all(&work_dir).await;
+            return Err(e);
+        }
+    };
+
+    let mut metadata = load_mods_metadata(app, &instance_id).await;
+    for entry in metadata.iter_mut() {
+        if !metadata_kind_matches(&entry.kind, "mods") {
+            continue;
+        }
+        if let Some(version) = versions.get(&entry.file_name) {
+            let file = version.files.iter().find(|f| f.primary).unwrap_or(&version.files[0]);
+            entry.file_name = file.filename.clone();
+            entry.version_id = Some(version.id.clone());
+            entry.dependencies = version
+                .dependencies
+                .iter()
+                .filter(|d| d.dependency_type == "required")
+                .filter_map(|d| d.version_id.clone().or_else(|| d.project_id.clone()))
+                .collect();
+        }
+    }
+    save_mods_metadata(app, &instance_id, &metadata).await;
+    let _ = tokio_fs::remove_dir_all(&work_dir).await;
+    refresh_mods_cache(app, &instance_id).await;
+
+    let _ = app.emit(
+        "download-progress",
+        ProgressPayload { task: "Instancia actualizada".to_string(), percent: 100.0 },
+    );
+    let _ = append_action_log(
+        app,
+        &format!(
+            "instance_upgrade instance={} from={} to={} upgraded={} unavailable={}",
+            instance_id,
+            preview.from_version,
+            resolved_version,
+            preview.upgrade_count,
+            preview.unavailable_count
+        ),
+    )
+    .await;
+    Ok(build_summary(app, &instance).await)
+}
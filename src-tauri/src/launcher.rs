@@ -1,11 +1,19 @@
 mod args;
 mod fs;
+mod game_log;
 mod java;
 mod launch;
 mod mods;
 mod natives;
 mod options;
+mod processes;
+mod settings;
 mod skins;
 mod version;
 
-pub use launch::launch_game_impl;
+pub(crate) use fs::is_launch_log;
+pub use game_log::GameLogBuffer;
+pub(crate) use game_log::{text_line_level, text_line_time, xml_line_timestamp};
+pub use launch::{get_effective_settings_impl, launch_game_impl};
+pub(crate) use mods::jar_loader_markers;
+pub use processes::{stop_game_impl, GameProcessRegistry};
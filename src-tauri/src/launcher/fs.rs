@@ -1,8 +1,9 @@
 use crate::error::AppResult;
 use crate::utils::get_launcher_dir;
+use flate2::write::GzEncoder;
+use flate2::Compression;
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::process::Stdio;
 use tauri::AppHandle;
 
 pub(crate) fn resolve_game_dir(
@@ -29,14 +30,73 @@ pub(crate) fn resolve_game_dir(
     Ok(base)
 }
 
-pub(crate) fn open_launch_log(base_dir: &Path) -> AppResult<(PathBuf, Stdio, Stdio)> {
-    let logs_dir = base_dir.join("logs");
+pub(crate) fn is_launch_log(name: &str) -> bool {
+    name.starts_with("launch-") && (name.ends_with(".log") || name.ends_with(".log.gz"))
+}
+
+fn gzip_file(path: &Path) -> std::io::Result<()> {
+    let mut gz_name = path.as_os_str().to_owned();
+    gz_name.push(".gz");
+    let mut input = fs::File::open(path)?;
+    let mut encoder =
+        GzEncoder::new(fs::File::create(PathBuf::from(&gz_name))?, Compression::default());
+    std::io::copy(&mut input, &mut encoder)?;
+    encoder.finish()?;
+    // Si el original sigue abierto (Windows) no se deja la copia comprimida al lado.
+    fs::remove_file(path).inspect_err(|_| {
+        let _ = fs::remove_file(PathBuf::from(&gz_name));
+    })
+}
+
+// Comprime los logs de lanzamientos anteriores y deja espacio para uno nuevo dentro de `keep`.
+// Los de juegos que siguen abiertos (`active`) no se tocan ni cuentan para el limite.
+fn rotate_launch_logs(logs_dir: &Path, keep: usize, active: &[PathBuf]) {
+    let mut names: Vec<String> = match fs::read_dir(logs_dir) {
+        Ok(entries) => entries
+            .flatten()
+            .filter(|e| !active.contains(&e.path()))
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .filter(|name| is_launch_log(name))
+            .collect(),
+        Err(_) => return,
+    };
+    for name in names.iter_mut().filter(|name| name.ends_with(".log")) {
+        if gzip_file(&logs_dir.join(&*name)).is_ok() {
+            name.push_str(".gz");
+        }
+    }
+    // La fecha va en el nombre, asi que el orden alfabetico es el cronologico.
+    names.sort();
+    let excess = names.len().saturating_sub(keep.saturating_sub(1));
+    for name in names.iter().take(excess) {
+        let _ = fs::remove_file(logs_dir.join(name));
+    }
+}
+
+/// Crea `<game_dir>/logs/launch-<fecha>.log` para la salida de este lanzamiento. `active` son
+/// los logs que todavia escriben otros juegos abiertos.
+pub(crate) fn open_launch_log(
+    game_dir: &Path,
+    keep: usize,
+    active: &[PathBuf],
+) -> AppResult<(PathBuf, fs::File)> {
+    let logs_dir = game_dir.join("logs");
     fs::create_dir_all(&logs_dir).map_err(|e| crate::error::AppError::Message(e.to_string()))?;
-    let log_path = logs_dir.join("launcher-latest.log");
+    rotate_launch_logs(&logs_dir, keep.max(1), active);
+    let stamp = chrono::Local::now().format("%Y-%m-%d_%H-%M-%S").to_string();
+    let base_name = format!("launch-{}", stamp);
+    let mut name = base_name.clone();
+    let mut n = 1;
+    while logs_dir.join(format!("{}.log", name)).exists()
+        || logs_dir.join(format!("{}.log.gz", name)).exists()
+    {
+        n += 1;
+        name = format!("{}-{}", base_name, n);
+    }
+    let log_path = logs_dir.join(format!("{}.log", name));
     let file =
         fs::File::create(&log_path).map_err(|e| crate::error::AppError::Message(e.to_string()))?;
-    let file_err = file.try_clone().map_err(|e| crate::error::AppError::Message(e.to_string()))?;
-    Ok((log_path, Stdio::from(file), Stdio::from(file_err)))
+    Ok((log_path, file))
 }
 
 pub(crate) async fn ensure_disk_space(
@@ -99,7 +159,7 @@ fn free_space_bytes(path: &Path) -> AppResult<u64> {
                 return Err("No se pudo obtener espacio libre".to_string().into());
             }
             let free = stat.f_bavail as u64 * stat.f_bsize as u64;
-            return Ok(free);
+            Ok(free)
         }
     }
 
@@ -0,0 +1,301 @@
+use crate::models::GameLogLine;
+use crate::state::AppState;
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager};
+
+// Suficiente para ponerse al dia con el arranque de un modpack grande.
+const GAME_LOG_CAPACITY: usize = 5000;
+
+/// Ultimas lineas de los juegos lanzados, para que una consola reabierta pueda ponerse al dia.
+#[derive(Default)]
+pub struct GameLogBuffer {
+    lines: Mutex<VecDeque<GameLogLine>>,
+    next_seq: AtomicU64,
+}
+
+impl GameLogBuffer {
+    fn push(&self, mut line: GameLogLine) -> GameLogLine {
+        line.seq = self.next_seq.fetch_add(1, Ordering::SeqCst) + 1;
+        if let Ok(mut lines) = self.lines.lock() {
+            if lines.len() >= GAME_LOG_CAPACITY {
+                lines.pop_front();
+            }
+            lines.push_back(line.clone());
+        }
+        line
+    }
+
+    /// Lineas con `seq` mayor a `since`, opcionalmente de una sola instancia.
+    pub fn snapshot(&self, instance_id: Option<&str>, since: Option<u64>) -> Vec<GameLogLine> {
+        let lines = match self.lines.lock() {
+            Ok(lines) => lines,
+            Err(_) => return Vec::new(),
+        };
+        lines
+            .iter()
+            .filter(|l| since.map(|s| l.seq > s).unwrap_or(true))
+            .filter(|l| instance_id.is_none() || l.instance_id.as_deref() == instance_id)
+            .cloned()
+            .collect()
+    }
+
+    pub fn clear(&self, instance_id: Option<&str>) {
+        if let Ok(mut lines) = self.lines.lock() {
+            match instance_id {
+                Some(id) => lines.retain(|l| l.instance_id.as_deref() != Some(id)),
+                None => lines.clear(),
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct ParsedLine {
+    level: Option<String>,
+    thread: Option<String>,
+    logger: Option<String>,
+    message: String,
+    timestamp: Option<i64>,
+}
+
+fn take_bracket(s: &str, open: char, close: char) -> Option<(&str, &str)> {
+    let rest = s.trim_start().strip_prefix(open)?;
+    let end = rest.find(close)?;
+    Some((&rest[..end], &rest[end + 1..]))
+}
+
+// Formatos de texto:
+//   vanilla  `[12:34:56] [Render thread/INFO]: mensaje`
+//   forge    `[29ene.2024 12:34:56.789] [main/INFO] [cpw.mods.modlauncher.Launcher/]: mensaje`
+//   fabric   `[12:34:56] [main/INFO] (FabricLoader) mensaje`
+fn parse_text_line(line: &str) -> ParsedLine {
+    let plain = || ParsedLine { message: line.to_string(), ..Default::default() };
+    let (_, rest) = match take_bracket(line, '[', ']') {
+        Some(parts) => parts,
+        None => return plain(),
+    };
+    let (thread_level, mut rest) = match take_bracket(rest, '[', ']') {
+        Some(parts) => parts,
+        None => return plain(),
+    };
+    let (thread, level) = match thread_level.rsplit_once('/') {
+        Some((thread, level)) => (thread, level),
+        None => return plain(),
+    };
+    let mut logger = None;
+    if let Some((name, after)) =
+        take_bracket(rest, '[', ']').or_else(|| take_bracket(rest, '(', ')'))
+    {
+        logger = Some(name.trim_end_matches('/').to_string()).filter(|n| !n.is_empty());
+        rest = after;
+    }
+    let message = rest.trim_start();
+    ParsedLine {
+        level: Some(level.trim().to_uppercase()),
+        thread: Some(thread.to_string()),
+        logger,
+        message: message.strip_prefix(':').unwrap_or(message).trim_start().to_string(),
+        timestamp: None,
+    }
+}
+
+/// Nivel de una linea de log en texto, si tiene el formato de log4j.
+pub(crate) fn text_line_level(line: &str) -> Option<String> {
+    parse_text_line(line)
+        .level
+        .filter(|l| matches!(l.as_str(), "TRACE" | "DEBUG" | "INFO" | "WARN" | "ERROR" | "FATAL"))
+}
+
+/// Hora con la que empieza una linea de texto (`[12:34:56]` o `[29ene.2024 12:34:56.789]`).
+pub(crate) fn text_line_time(line: &str) -> Option<chrono::NaiveTime> {
+    let (stamp, _) = take_bracket(line, '[', ']')?;
+    let clock = stamp.rsplit(' ').next()?.split('.').next()?;
+    chrono::NaiveTime::parse_from_str(clock, "%H:%M:%S").ok()
+}
+
+/// Millis del atributo `timestamp` si la linea abre un evento del layout XML de log4j.
+pub(crate) fn xml_line_timestamp(line: &str) -> Option<i64> {
+    let start = line.find("<log4j:Event")?;
+    let tag = &line[start..];
+    let tag = &tag[..tag.find('>').unwrap_or(tag.len())];
+    xml_attr(tag, "timestamp")?.parse().ok()
+}
+
+fn xml_attr(tag: &str, name: &str) -> Option<String> {
+    let key = format!("{}=\"", name);
+    let start = tag.find(&key)? + key.len();
+    let end = start + tag[start..].find('"')?;
+    Some(unescape_xml(&tag[start..end]))
+}
+
+fn unescape_xml(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+fn xml_element_text(xml: &str, tag: &str) -> Option<String> {
+    let open = format!("<{}>", tag);
+    let close = format!("</{}>", tag);
+    let start = xml.find(&open)? + open.len();
+    let end = start + xml[start..].find(&close)?;
+    let inner = xml[start..end].trim();
+    let text = match inner.strip_prefix("<![CDATA[").and_then(|s| s.strip_suffix("]]>")) {
+        Some(cdata) => cdata.to_string(),
+        None => unescape_xml(inner),
+    };
+    Some(text)
+}
+
+// Evento del layout XML de log4j que usa vanilla con `-Dlog4j.configurationFile`.
+fn parse_xml_event(xml: &str) -> ParsedLine {
+    let tag_end = xml.find('>').unwrap_or(xml.len());
+    let tag = &xml[..tag_end];
+    let mut message = xml_element_text(xml, "log4j:Message").unwrap_or_default();
+    if let Some(throwable) = xml_element_text(xml, "log4j:Throwable") {
+        message = format!("{}\n{}", message, throwable.trim_end());
+    }
+    ParsedLine {
+        level: xml_attr(tag, "level"),
+        thread: xml_attr(tag, "thread"),
+        logger: xml_attr(tag, "logger"),
+        message,
+        timestamp: xml_attr(tag, "timestamp").and_then(|t| t.parse().ok()),
+    }
+}
+
+/// Datos comunes a todas las lineas de un proceso del juego.
+#[derive(Clone)]
+pub(crate) struct GameLogContext {
+    pub app: AppHandle,
+    pub pid: u32,
+    pub instance_id: Option<String>,
+    pub file: Arc<Mutex<File>>,
+}
+
+impl GameLogContext {
+    fn emit(&self, stream: &str, parsed: ParsedLine) {
+        let line = GameLogLine {
+            seq: 0,
+            pid: self.pid,
+            instance_id: self.instance_id.clone(),
+            stream: stream.to_string(),
+            level: parsed.level,
+            thread: parsed.thread,
+            logger: parsed.logger,
+            message: parsed.message,
+            timestamp: parsed.timestamp.unwrap_or_else(|| chrono::Utc::now().timestamp_millis()),
+        };
+        let line = self.app.state::<AppState>().game_logs.push(line);
+        let _ = self.app.emit("game-log", line);
+    }
+}
+
+/// Lee la salida del proceso hasta que se cierra: copia cada linea al log del launcher y
+/// emite un evento `game-log` por linea (o por evento XML completo).
+pub(crate) fn pump_game_output<R: Read>(ctx: GameLogContext, stream: &'static str, reader: R) {
+    let mut reader = BufReader::new(reader);
+    let mut raw = Vec::new();
+    let mut xml_event: Option<String> = None;
+    loop {
+        raw.clear();
+        match reader.read_until(b'\n', &mut raw) {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {}
+        }
+        if let Ok(mut file) = ctx.file.lock() {
+            let _ = file.write_all(&raw);
+        }
+        let line = String::from_utf8_lossy(&raw);
+        let line = line.trim_end_matches(['\r', '\n']);
+
+        if let Some(event) = xml_event.as_mut() {
+            event.push('\n');
+            event.push_str(line);
+            if line.contains("</log4j:Event>") {
+                let event = xml_event.take().unwrap_or_default();
+                ctx.emit(stream, parse_xml_event(&event));
+            }
+            continue;
+        }
+        if line.trim_start().starts_with("<log4j:Event") {
+            if line.contains("</log4j:Event>") {
+                ctx.emit(stream, parse_xml_event(line.trim_start()));
+            } else {
+                xml_event = Some(line.trim_start().to_string());
+            }
+            continue;
+        }
+        if line.is_empty() {
+            continue;
+        }
+        ctx.emit(stream, parse_text_line(line));
+    }
+    // Evento XML cortado porque el proceso termino a mitad.
+    if let Some(event) = xml_event {
+        ctx.emit(stream, parse_xml_event(&event));
+    }
+    if let Ok(mut file) = ctx.file.lock() {
+        let _ = file.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveTime;
+
+    #[test]
+    fn text_line_level_reads_vanilla_forge_and_fabric() {
+        assert_eq!(
+            text_line_level("[12:34:56] [Render thread/INFO]: hola").as_deref(),
+            Some("INFO")
+        );
+        assert_eq!(
+            text_line_level("[29ene.2024 12:34:56.789] [main/warn] [cpw.mods.Launcher/]: x")
+                .as_deref(),
+            Some("WARN")
+        );
+        assert_eq!(
+            text_line_level("[12:34:56] [main/ERROR] (FabricLoader) x").as_deref(),
+            Some("ERROR")
+        );
+    }
+
+    #[test]
+    fn text_line_level_ignores_other_lines() {
+        assert_eq!(text_line_level("\tat net.minecraft.Main.main(Main.java:1)"), None);
+        assert_eq!(text_line_level("[12:34:56] [main/nivel]: x"), None);
+    }
+
+    #[test]
+    fn text_line_time_reads_both_stamps() {
+        let expected = NaiveTime::from_hms_opt(12, 34, 56);
+        assert_eq!(text_line_time("[12:34:56] [main/INFO]: x"), expected);
+        assert_eq!(text_line_time("[29ene.2024 12:34:56.789] [main/INFO]: x"), expected);
+        assert_eq!(text_line_time("sin hora"), None);
+    }
+
+    #[test]
+    fn xml_line_timestamp_reads_event_tag() {
+        let line = r#"<log4j:Event logger="net.minecraft" timestamp="1700000000123" level="INFO" thread="main">"#;
+        assert_eq!(xml_line_timestamp(line), Some(1700000000123));
+        assert_eq!(xml_line_timestamp(r#"<log4j:Message timestamp="1">"#), None);
+        assert_eq!(xml_line_timestamp("[12:34:56] [main/INFO]: x"), None);
+    }
+
+    #[test]
+    fn parse_xml_event_reads_cdata_message() {
+        let xml = r#"<log4j:Event logger="a" timestamp="5" level="WARN" thread="main"><log4j:Message><![CDATA[a < b]]></log4j:Message></log4j:Event>"#;
+        let parsed = parse_xml_event(xml);
+        assert_eq!(parsed.level.as_deref(), Some("WARN"));
+        assert_eq!(parsed.timestamp, Some(5));
+        assert_eq!(parsed.message, "a < b");
+    }
+}
@@ -1,26 +1,44 @@
 use crate::downloader::download_libraries_concurrent;
 use crate::error::AppResult;
+use crate::instances::{get_instance_impl, repair_shared_folders};
 use crate::models::{
-    GameProcessPayload, GameSettings, MinecraftProfile, ProgressPayload, VersionManifest,
-    VersionMetadata,
+    EffectiveGameSettings, GameProcessPayload, GameSettings, MinecraftProfile, ProgressPayload,
+    RunningGame, VersionManifest, VersionMetadata,
 };
-use crate::utils::{get_launcher_dir, hide_background_window};
-use std::process::Command;
-use std::sync::Mutex;
-use tauri::{AppHandle, Emitter};
+use crate::playtime::record_play_session;
+use crate::state::AppState;
+use crate::utils::{append_action_log, get_launcher_dir, hide_background_window};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager};
 use tokio::fs as tokio_fs;
 
 use super::args::{build_arguments, build_classpath};
 use super::fs::{ensure_disk_space, open_launch_log, resolve_game_dir};
+use super::game_log::{pump_game_output, GameLogContext};
 use super::java::{ensure_java_runtime, resolve_java_binary, resolve_required_java_version};
-use super::mods::detect_mod_loader_conflicts;
+use super::mods::{detect_duplicate_mods, detect_mod_loader_conflicts};
 use super::natives::ensure_natives;
 use super::options::apply_options_settings;
+use super::settings::{describe_sources, merge_game_settings};
 use super::skins::prepare_offline_skin_pack;
 use super::version::{
     extract_base_version, mc_minor_from_version_id, resolve_version, should_skip_game_jar,
 };
 
+pub async fn get_effective_settings_impl(
+    app: &AppHandle,
+    instance_id: Option<String>,
+    settings: Option<GameSettings>,
+) -> AppResult<EffectiveGameSettings> {
+    let instance_settings = match instance_id.as_deref() {
+        Some(id) => get_instance_impl(app, id).await?.settings,
+        None => None,
+    };
+    Ok(merge_game_settings(settings, instance_settings.as_ref()))
+}
+
 pub async fn launch_game_impl(
     app: &AppHandle,
     version_id: String,
@@ -38,14 +56,16 @@ pub async fn launch_game_impl(
             .ok_or_else(|| crate::error::AppError::Message("No has iniciado sesion.".to_string()))?
     };
 
-    let settings = settings.unwrap_or(GameSettings {
-        resolution: crate::models::Resolution { width: 1280, height: 720 },
-        fullscreen: true,
-        memory: crate::models::MemorySettings { min_gb: 1, max_gb: 2 },
-        java_args: String::new(),
-        java_path: String::new(),
-        max_fps: 120,
-    });
+    let effective = get_effective_settings_impl(app, instance_id.clone(), settings).await?;
+    let _ = app.emit("launch-settings", effective.clone());
+    if let Some(id) = instance_id.as_deref() {
+        let _ = append_action_log(
+            app,
+            &format!("launch_settings instance={} {}", id, describe_sources(&effective.sources)),
+        )
+        .await;
+    }
+    let settings = effective.settings;
 
     let resolved = resolve_version(app, &version_id, metadata_cache)?;
     let required_java =
@@ -80,11 +100,23 @@ pub async fn launch_game_impl(
     tokio_fs::create_dir_all(&game_dir)
         .await
         .map_err(|e| crate::error::AppError::Message(e.to_string()))?;
+    if let Some(id) = instance_id.as_deref() {
+        if let Err(e) = repair_shared_folders(app, id).await {
+            let _ = append_action_log(
+                app,
+                &format!("instance_shared_repair_failed instance={} error={}", id, e),
+            )
+            .await;
+        }
+    }
     if instance_id.is_some() || is_forge || is_neoforge || is_fabric {
         let _ = tokio_fs::create_dir_all(game_dir.join("mods")).await;
         let _ = tokio_fs::create_dir_all(game_dir.join("config")).await;
     }
     detect_mod_loader_conflicts(&game_dir, is_forge, is_neoforge, is_fabric).await?;
+    if is_forge || is_neoforge || is_fabric {
+        detect_duplicate_mods(&game_dir).await?;
+    }
 
     let _ = app.emit(
         "download-progress",
@@ -150,16 +182,24 @@ pub async fn launch_game_impl(
         ProgressPayload { task: "Lanzando Minecraft...".to_string(), percent: 100.0 },
     );
 
-    let (log_path, log_out, log_err) = tokio::task::spawn_blocking({
-        let base_dir = base_dir.clone();
-        move || open_launch_log(&base_dir)
+    let (log_path, log_file) = tokio::task::spawn_blocking({
+        let game_dir = game_dir.clone();
+        let keep = settings.launch_logs_keep as usize;
+        let active: Vec<PathBuf> = app
+            .state::<AppState>()
+            .processes
+            .list()
+            .into_iter()
+            .map(|g| PathBuf::from(g.log_path))
+            .collect();
+        move || open_launch_log(&game_dir, keep, &active)
     })
     .await
     .map_err(|e| crate::error::AppError::Message(e.to_string()))??;
     let java_bin = resolve_java_binary(&settings, required_java.as_ref(), &base_dir)?;
 
     let mut cmd = Command::new(java_bin);
-    cmd.args(args).current_dir(&game_dir).stdout(log_out).stderr(log_err);
+    cmd.args(args).current_dir(&game_dir).stdout(Stdio::piped()).stderr(Stdio::piped());
     hide_background_window(&mut cmd);
 
     let mut child = cmd.spawn().map_err(|e| {
@@ -167,12 +207,52 @@ pub async fn launch_game_impl(
     })?;
 
     let pid = child.id();
+    let started_at = chrono::Utc::now().timestamp_millis();
+    app.state::<AppState>().processes.insert(RunningGame {
+        pid,
+        instance_id: instance_id.clone(),
+        version: version_id.clone(),
+        started_at,
+        log_path: log_path.to_string_lossy().to_string(),
+    });
     let _ = app.emit("game-started", GameProcessPayload { pid, code: None });
 
+    // La salida pasa por el launcher: se copia al log y se emite linea a linea.
+    let log_ctx = GameLogContext {
+        app: app.clone(),
+        pid,
+        instance_id: instance_id.clone(),
+        file: Arc::new(Mutex::new(log_file)),
+    };
+    if let Some(stdout) = child.stdout.take() {
+        let ctx = log_ctx.clone();
+        std::thread::spawn(move || pump_game_output(ctx, "stdout", stdout));
+    }
+    if let Some(stderr) = child.stderr.take() {
+        let ctx = log_ctx;
+        std::thread::spawn(move || pump_game_output(ctx, "stderr", stderr));
+    }
+
     let app_handle = app.clone();
+    let session_instance = instance_id.clone();
+    let session_version = version_id.clone();
+    let session_dir = game_dir.clone();
     std::thread::spawn(move || {
         let status = child.wait().ok();
         let code = status.and_then(|s| s.code());
+        if let Some(id) = session_instance.as_deref() {
+            if let Err(e) = record_play_session(
+                &app_handle,
+                id,
+                &session_dir,
+                &session_version,
+                started_at,
+                code,
+            ) {
+                eprintln!("No se pudo registrar la sesion de juego: {}", e);
+            }
+        }
+        app_handle.state::<AppState>().processes.remove(pid);
         let _ = app_handle.emit("game-exited", GameProcessPayload { pid, code });
     });
 
@@ -58,6 +58,90 @@ pub(crate) async fn detect_mod_loader_conflicts(
     Ok(())
 }
 
+/// Falla si hay varios jars activos con el mismo mod id (p. ej. tras actualizar a mano).
+pub(crate) async fn detect_duplicate_mods(game_dir: &Path) -> AppResult<()> {
+    if !game_dir.join("mods").exists() {
+        return Ok(());
+    }
+    let game_dir = game_dir.to_path_buf();
+    let groups =
+        tokio::task::spawn_blocking(move || crate::content::find_duplicate_mods(&game_dir))
+            .await
+            .map_err(|e| crate::error::AppError::Message(e.to_string()))?;
+    if groups.is_empty() {
+        return Ok(());
+    }
+    Err(format!(
+        "Hay mods duplicados: {}. Resuelve los duplicados para conservar solo la version mas nueva.",
+        crate::content::describe_duplicates(&groups)
+    )
+    .into())
+}
+
+/// Archivos de metadatos de loader presentes en un jar.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct JarLoaderMarkers {
+    pub fabric: bool,
+    pub forge: bool,
+    pub neoforge: bool,
+}
+
+impl JarLoaderMarkers {
+    /// True si el jar solo declara loaders distintos de `loader` (los multi-loader se aceptan).
+    pub(crate) fn excludes_loader(&self, loader: &str) -> bool {
+        if !self.fabric && !self.forge && !self.neoforge {
+            return false;
+        }
+        match loader {
+            "fabric" => !self.fabric,
+            "forge" => !self.forge,
+            // Igual que al lanzar: NeoForge carga muchos jars de Forge, solo se rechaza Fabric puro.
+            "neoforge" => self.fabric && !self.forge && !self.neoforge,
+            _ => false,
+        }
+    }
+
+    pub(crate) fn describe(&self) -> String {
+        let mut loaders = Vec::new();
+        if self.fabric {
+            loaders.push("fabric");
+        }
+        if self.forge {
+            loaders.push("forge");
+        }
+        if self.neoforge {
+            loaders.push("neoforge");
+        }
+        loaders.join("/")
+    }
+}
+
+/// Devuelve None si el archivo no se puede abrir como zip.
+pub(crate) fn jar_loader_markers(path: &Path) -> Option<JarLoaderMarkers> {
+    let file = fs::File::open(path).ok()?;
+    let mut archive = zip::ZipArchive::new(file).ok()?;
+    let mut markers = JarLoaderMarkers::default();
+    for i in 0..archive.len() {
+        let name = match archive.by_index(i) {
+            Ok(file) => file.name().to_string(),
+            Err(_) => continue,
+        };
+        if name.eq_ignore_ascii_case("fabric.mod.json") {
+            markers.fabric = true;
+        }
+        if name.eq_ignore_ascii_case("META-INF/mods.toml") {
+            markers.forge = true;
+        }
+        if name.eq_ignore_ascii_case("META-INF/neoforge.mods.toml") {
+            markers.neoforge = true;
+        }
+        if markers.fabric && markers.forge && markers.neoforge {
+            break;
+        }
+    }
+    Some(markers)
+}
+
 fn scan_mods_for_loader(mods_dir: &Path) -> AppResult<ModScanResult> {
     let mut fabric_only = Vec::new();
     let mut forge_only = Vec::new();
@@ -80,36 +164,14 @@ fn scan_mods_for_loader(mods_dir: &Path) -> AppResult<ModScanResult> {
 
         let file_name = path.file_name().and_then(|s| s.to_str()).unwrap_or("mod.jar").to_string();
 
-        let file = match fs::File::open(&path) {
-            Ok(f) => f,
-            Err(_) => continue,
+        let JarLoaderMarkers {
+            fabric: is_fabric,
+            forge: has_forge_meta,
+            neoforge: has_neoforge_meta,
+        } = match jar_loader_markers(&path) {
+            Some(markers) => markers,
+            None => continue,
         };
-        let mut archive = match zip::ZipArchive::new(file) {
-            Ok(z) => z,
-            Err(_) => continue,
-        };
-
-        let mut is_fabric = false;
-        let mut has_forge_meta = false;
-        let mut has_neoforge_meta = false;
-        for i in 0..archive.len() {
-            let name = match archive.by_index(i) {
-                Ok(file) => file.name().to_string(),
-                Err(_) => continue,
-            };
-            if name.eq_ignore_ascii_case("fabric.mod.json") {
-                is_fabric = true;
-            }
-            if name.eq_ignore_ascii_case("META-INF/mods.toml") {
-                has_forge_meta = true;
-            }
-            if name.eq_ignore_ascii_case("META-INF/neoforge.mods.toml") {
-                has_neoforge_meta = true;
-            }
-            if is_fabric && has_forge_meta && has_neoforge_meta {
-                break;
-            }
-        }
 
         if is_fabric && !has_forge_meta && !has_neoforge_meta && fabric_only.len() < 4 {
             fabric_only.push(file_name.clone());
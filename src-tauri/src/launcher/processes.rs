@@ -0,0 +1,121 @@
+use crate::error::{AppError, AppResult};
+use crate::models::RunningGame;
+use crate::state::AppState;
+use crate::utils::append_action_log;
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use sysinfo::{Pid, System};
+use tauri::{AppHandle, Manager};
+
+/// Procesos del juego lanzados por el launcher, por instancia (o `version:<id>` si se lanzo
+/// una version sin instancia). Cada proceso sale de aca cuando termina.
+#[derive(Default)]
+pub struct GameProcessRegistry {
+    games: Mutex<HashMap<String, Vec<RunningGame>>>,
+    // Instancias que estan preparando el lanzamiento y todavia no tienen proceso.
+    starting: Mutex<HashSet<String>>,
+}
+
+/// Mientras existe, la instancia cuenta como "iniciandose" para bloquear un segundo lanzamiento.
+pub struct LaunchReservation<'a> {
+    registry: &'a GameProcessRegistry,
+    instance_id: Option<String>,
+}
+
+impl Drop for LaunchReservation<'_> {
+    fn drop(&mut self) {
+        if let Some(id) = self.instance_id.take() {
+            if let Ok(mut starting) = self.registry.starting.lock() {
+                starting.remove(&id);
+            }
+        }
+    }
+}
+
+fn registry_key(game: &RunningGame) -> String {
+    game.instance_id.clone().unwrap_or_else(|| format!("version:{}", game.version))
+}
+
+impl GameProcessRegistry {
+    /// Falla si la instancia ya esta en ejecucion (o iniciandose) y no admite varias a la vez.
+    pub fn begin_launch(
+        &self,
+        instance_id: &str,
+        allow_multiple: bool,
+    ) -> AppResult<LaunchReservation<'_>> {
+        if allow_multiple {
+            return Ok(LaunchReservation { registry: self, instance_id: None });
+        }
+        let games = self.games.lock().map_err(|_| "Error registro de procesos".to_string())?;
+        let mut starting =
+            self.starting.lock().map_err(|_| "Error registro de procesos".to_string())?;
+        if let Some(running) = games.get(instance_id).and_then(|list| list.first()) {
+            return Err(format!("La instancia ya esta en ejecucion (PID {})", running.pid).into());
+        }
+        if !starting.insert(instance_id.to_string()) {
+            return Err("La instancia ya se esta iniciando".to_string().into());
+        }
+        Ok(LaunchReservation { registry: self, instance_id: Some(instance_id.to_string()) })
+    }
+
+    pub(crate) fn insert(&self, game: RunningGame) {
+        if let Ok(mut games) = self.games.lock() {
+            games.entry(registry_key(&game)).or_default().push(game);
+        }
+    }
+
+    pub(crate) fn remove(&self, pid: u32) {
+        if let Ok(mut games) = self.games.lock() {
+            for list in games.values_mut() {
+                list.retain(|g| g.pid != pid);
+            }
+            games.retain(|_, list| !list.is_empty());
+        }
+    }
+
+    fn find(&self, pid: u32) -> Option<RunningGame> {
+        let games = self.games.lock().ok()?;
+        games.values().flatten().find(|g| g.pid == pid).cloned()
+    }
+
+    /// Juegos en ejecucion, del mas viejo al mas nuevo.
+    pub fn list(&self) -> Vec<RunningGame> {
+        let mut out: Vec<RunningGame> = match self.games.lock() {
+            Ok(games) => games.values().flatten().cloned().collect(),
+            Err(_) => Vec::new(),
+        };
+        out.sort_by_key(|g| g.started_at);
+        out
+    }
+}
+
+/// Cierra a la fuerza un juego lanzado por el launcher. Solo acepta PIDs del registro para no
+/// matar procesos ajenos; el registro se limpia cuando el hilo que espera al proceso lo ve salir.
+pub async fn stop_game_impl(app: &AppHandle, pid: u32) -> AppResult<String> {
+    let state = app.state::<AppState>();
+    let game = state
+        .processes
+        .find(pid)
+        .ok_or_else(|| AppError::Message("No hay un juego en ejecucion con ese PID".to_string()))?;
+
+    let killed = {
+        let mut sys = System::new();
+        sys.refresh_processes();
+        match sys.process(Pid::from_u32(pid)) {
+            Some(process) => process.kill(),
+            None => {
+                // Ya no existe: el hilo de espera lo quitara, pero se adelanta la limpieza.
+                state.processes.remove(pid);
+                return Ok("El juego ya se habia cerrado".to_string());
+            }
+        }
+    };
+    if !killed {
+        return Err(format!("No se pudo cerrar el proceso {}", pid).into());
+    }
+
+    let instance = game.instance_id.as_deref().unwrap_or("-");
+    let _ =
+        append_action_log(app, &format!("game_force_stop pid={} instance={}", pid, instance)).await;
+    Ok(format!("Juego cerrado (PID {})", pid))
+}
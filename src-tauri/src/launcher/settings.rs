@@ -0,0 +1,89 @@
+use crate::models::{
+    EffectiveGameSettings, GameSettings, InstanceSettings, MemorySettings, Resolution,
+    SettingSource, SettingsSources,
+};
+
+fn default_game_settings() -> GameSettings {
+    GameSettings {
+        resolution: Resolution { width: 1280, height: 720 },
+        fullscreen: true,
+        memory: MemorySettings { min_gb: 1, max_gb: 2 },
+        java_args: String::new(),
+        java_path: String::new(),
+        max_fps: 120,
+        launch_logs_keep: 10,
+    }
+}
+
+fn pick<T>(instance: Option<T>, global: Option<T>, fallback: T) -> (T, SettingSource) {
+    if let Some(value) = instance {
+        return (value, SettingSource::Instance);
+    }
+    if let Some(value) = global {
+        return (value, SettingSource::Global);
+    }
+    (fallback, SettingSource::Default)
+}
+
+// Combina los ajustes globales del frontend con los de la instancia, campo por campo.
+pub(crate) fn merge_game_settings(
+    global: Option<GameSettings>,
+    instance: Option<&InstanceSettings>,
+) -> EffectiveGameSettings {
+    let defaults = default_game_settings();
+    let overrides = instance.cloned().unwrap_or_default();
+    let global = global.as_ref();
+
+    let (resolution, resolution_src) =
+        pick(overrides.resolution, global.map(|g| g.resolution.clone()), defaults.resolution);
+    let (fullscreen, fullscreen_src) =
+        pick(None, global.map(|g| g.fullscreen), defaults.fullscreen);
+    let (memory, memory_src) =
+        pick(overrides.memory, global.map(|g| g.memory.clone()), defaults.memory);
+    let (java_args, java_args_src) =
+        pick(overrides.java_args, global.map(|g| g.java_args.clone()), defaults.java_args);
+    let (java_path, java_path_src) =
+        pick(overrides.java_path, global.map(|g| g.java_path.clone()), defaults.java_path);
+    let (max_fps, max_fps_src) =
+        pick(overrides.max_fps, global.map(|g| g.max_fps), defaults.max_fps);
+    let launch_logs_keep = global.map(|g| g.launch_logs_keep).unwrap_or(defaults.launch_logs_keep);
+
+    EffectiveGameSettings {
+        settings: GameSettings {
+            resolution,
+            fullscreen,
+            memory,
+            java_args,
+            java_path,
+            max_fps,
+            launch_logs_keep,
+        },
+        sources: SettingsSources {
+            resolution: resolution_src,
+            fullscreen: fullscreen_src,
+            memory: memory_src,
+            java_args: java_args_src,
+            java_path: java_path_src,
+            max_fps: max_fps_src,
+        },
+    }
+}
+
+pub(crate) fn describe_sources(sources: &SettingsSources) -> String {
+    fn label(source: SettingSource) -> &'static str {
+        match source {
+            SettingSource::Instance => "instance",
+            SettingSource::Global => "global",
+            SettingSource::Default => "default",
+        }
+    }
+    format!(
+        "resolution={} fullscreen={} memory={} javaArgs={} javaPath={} maxFps={}",
+        label(sources.resolution),
+        label(sources.fullscreen),
+        label(sources.memory),
+        label(sources.java_args),
+        label(sources.java_path),
+        label(sources.max_fps),
+    )
+}
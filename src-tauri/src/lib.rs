@@ -16,9 +16,12 @@ mod models;
 mod modrinth;
 mod neoforge;
 mod optimization;
+mod playtime;
+mod prism;
 mod repair;
 mod skins;
 mod state;
+mod templates;
 mod utils;
 mod worlds;
 
@@ -156,6 +159,10 @@ pub fn run() {
             // Sistema
             detect_system_java,
             launch_game,
+            get_game_log,
+            clear_game_log,
+            list_running_games,
+            stop_game,
             // Discord
             discord_init,
             discord_set_activity,
@@ -164,26 +171,68 @@ pub fn run() {
             // Instancias
             list_instances,
             list_instance_content,
+            get_mod_icon,
             toggle_instance_content,
             delete_instance_content,
+            import_instance_content,
+            pin_instance_content,
+            unpin_instance_content,
+            preview_content_dependencies,
+            list_orphaned_mods,
+            list_duplicate_mods,
+            resolve_duplicate_mods,
+            list_mod_sets,
+            create_mod_set,
+            check_mod_set,
+            apply_mod_set,
+            delete_mod_set,
+            start_mod_bisect,
+            get_mod_bisect,
+            mark_mod_bisect,
+            end_mod_bisect,
             open_instance_content_folder,
             list_instance_reports,
             read_instance_report,
+            analyze_instance_report,
+            search_instance_logs,
             get_runtime_metrics,
             create_instance,
             update_instance,
+            get_effective_settings,
+            duplicate_instance,
+            set_instance_shared_folders,
+            list_instance_templates,
+            save_instance_template,
+            delete_instance_template,
+            create_instance_from_template,
+            preview_instance_upgrade,
+            apply_instance_upgrade,
+            import_prism_instance,
+            export_prism_instance,
             delete_instance,
+            list_trashed_instances,
+            restore_trashed_instance,
+            purge_trashed_instances,
+            get_trash_config,
+            set_trash_config,
+            set_instance_allow_multiple,
             open_instance_folder,
+            get_instance_playtime,
             clear_cache,
             close_splash,
             repair_instance,
             generate_diagnostic_report,
             upload_diagnostic_report,
+            get_redaction_config,
+            set_redaction_config,
             // Modrinth
             modrinth_search,
             modrinth_list_versions,
             modrinth_get_project,
             modrinth_install_version,
+            identify_instance_content,
+            check_content_updates,
+            apply_content_updates,
             modrinth_install_modpack,
             modrinth_install_modpack_with_backup,
             import_modpack_mrpack,
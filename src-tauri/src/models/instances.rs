@@ -1,3 +1,4 @@
+use super::InstanceSettings;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -12,6 +13,13 @@ pub struct Instance {
     pub last_played: Option<i64>,
     #[serde(default)]
     pub mods_cached_count: Option<u32>,
+    #[serde(default)]
+    pub settings: Option<InstanceSettings>,
+    #[serde(default)]
+    pub shared_folders: Vec<String>,
+    /// Permite lanzar la instancia otra vez mientras ya esta en ejecucion.
+    #[serde(default)]
+    pub allow_multiple: bool,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -25,6 +33,13 @@ pub struct InstanceSummary {
     pub created_at: i64,
     pub last_played: Option<i64>,
     pub mods_count: u32,
+    #[serde(default)]
+    pub settings: Option<InstanceSettings>,
+    #[serde(default)]
+    pub shared_folders: Vec<String>,
+    /// Permite lanzar la instancia otra vez mientras ya esta en ejecucion.
+    #[serde(default)]
+    pub allow_multiple: bool,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -40,6 +55,149 @@ pub struct InstanceContentItem {
     pub source: Option<String>,
     pub project_id: Option<String>,
     pub version_id: Option<String>,
+    #[serde(default)]
+    pub mod_info: Option<ModJarInfo>,
+    #[serde(default)]
+    pub pinned: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ModJarDependency {
+    pub mod_id: String,
+    #[serde(default)]
+    pub version_range: Option<String>,
+    pub required: bool,
+}
+
+/// Metadatos leidos del propio jar (fabric.mod.json, quilt.mod.json o mods.toml).
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ModJarInfo {
+    pub loader: String,
+    pub mod_id: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub authors: Vec<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub dependencies: Vec<ModJarDependency>,
+    /// Ruta del icono dentro del jar; la imagen se pide aparte con `get_mod_icon`.
+    #[serde(default)]
+    pub icon_path: Option<String>,
+}
+
+/// Efecto de quitar, desactivar o activar un mod segun el grafo de mods.json.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ContentDependencyReport {
+    pub file_name: String,
+    /// Mods activos que dejarian de funcionar (incluye dependientes indirectos).
+    pub dependents: Vec<String>,
+    /// Librerias que se quedarian sin ningun mod activo que las use.
+    pub orphans: Vec<String>,
+    /// Dependencias desactivadas que harian falta para activarlo.
+    pub disabled_dependencies: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ModSetEntry {
+    pub kind: String,
+    /// Nombre sin el sufijo `.disabled`.
+    pub file_name: String,
+    pub enabled: bool,
+}
+
+/// Estado activado/desactivado guardado de mods, resource packs y shaders.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ModSet {
+    pub name: String,
+    #[serde(default)]
+    pub entries: Vec<ModSetEntry>,
+    pub created_at: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ModSetApplyResult {
+    pub changed: u32,
+    /// Archivos del set que ya no estan en la instancia.
+    pub missing: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+/// Archivo local a copiar en mods/, resourcepacks/ o shaderpacks/.
+/// Se indica `path` o bien `file_name` + `data_base64`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ContentImportRequest {
+    pub kind: String,
+    #[serde(default)]
+    pub path: Option<String>,
+    #[serde(default)]
+    pub file_name: Option<String>,
+    #[serde(default)]
+    pub data_base64: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ContentImportResult {
+    pub file_name: String,
+    pub kind: String,
+    /// True si ya habia un archivo identico y no se copio nada.
+    pub duplicate: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DuplicateModFile {
+    pub file_name: String,
+    pub version: Option<String>,
+    pub modified: i64,
+}
+
+/// Varios jars activos que declaran el mismo mod id.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DuplicateModGroup {
+    pub mod_id: String,
+    pub name: Option<String>,
+    pub files: Vec<DuplicateModFile>,
+    /// Archivo que se conserva al resolver (la version mas nueva).
+    pub keep: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DuplicateResolveResult {
+    /// Jars movidos a `.launcher/replaced/<fecha>/mods/`.
+    pub removed: Vec<String>,
+    /// Mod ids que se dejaron como estaban porque habria que quitar un jar fijado.
+    pub unresolved: Vec<String>,
+}
+
+/// Sesion de busqueda binaria del mod que rompe el juego. Se guarda en `.launcher/bisect.json`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ModBisectSession {
+    pub started_at: i64,
+    /// Estado de `mods/` antes de empezar; se restaura al terminar o cancelar.
+    pub original: Vec<ModSetEntry>,
+    /// Mods que todavia pueden ser los culpables.
+    pub candidates: Vec<String>,
+    /// Mitad de `candidates` activa en la prueba actual.
+    pub testing: Vec<String>,
+    /// True cuando la primera mitad ya dio "bien" y se prueba la otra.
+    #[serde(default)]
+    pub second_half: bool,
+    /// Grupos que quedan activos mientras se reduce `candidates`: el fallo necesita mods de
+    /// mas de un grupo. Se reducen despues, uno por uno.
+    #[serde(default)]
+    pub held: Vec<Vec<String>>,
+    /// Culpables ya aislados; siguen activos en las pruebas siguientes.
+    #[serde(default)]
+    pub found: Vec<String>,
+    pub steps: u32,
+    #[serde(default)]
+    pub finished: bool,
+    /// Mod (o conjunto minimo) culpable cuando `finished` es true.
+    #[serde(default)]
+    pub result: Vec<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -50,6 +208,78 @@ pub struct InstanceLogEntry {
     pub modified: i64,
 }
 
+/// Posicion desde la que sigue una busqueda en logs: archivo y lineas ya recorridas.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LogSearchCursor {
+    pub kind: String,
+    pub file: String,
+    pub line: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LogSearchQuery {
+    /// Texto a buscar, o expresion regular si `regex` es true.
+    pub query: String,
+    #[serde(default)]
+    pub regex: bool,
+    #[serde(default)]
+    pub case_sensitive: bool,
+    /// Nivel minimo (DEBUG, INFO, WARN, ERROR, FATAL).
+    pub level: Option<String>,
+    /// Ventana de tiempo en millis.
+    pub since: Option<i64>,
+    pub until: Option<i64>,
+    /// Lineas de contexto antes y despues de cada resultado.
+    pub context: Option<usize>,
+    pub limit: Option<usize>,
+    pub cursor: Option<LogSearchCursor>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LogSearchMatch {
+    pub kind: String,
+    pub file: String,
+    /// Numero de linea, empezando en 1.
+    pub line: usize,
+    pub text: String,
+    pub level: Option<String>,
+    pub before: Vec<String>,
+    pub after: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LogSearchPage {
+    pub matches: Vec<LogSearchMatch>,
+    /// Ausente cuando ya no quedan archivos por recorrer.
+    pub next_cursor: Option<LogSearchCursor>,
+    pub scanned_files: usize,
+    pub total_files: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CrashCause {
+    /// missing_dependency | java_version | out_of_memory | mixin | suspected_mod | exception
+    pub kind: String,
+    pub summary: String,
+    /// Archivos de mods/ relacionados con la causa.
+    #[serde(default)]
+    pub files: Vec<String>,
+    pub hint: String,
+}
+
+/// Resultado de analizar un crash report o `latest.log`. Las causas van de mas a menos segura.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CrashAnalysis {
+    pub kind: String,
+    pub name: String,
+    pub exception: Option<String>,
+    /// Ultimo "Caused by:" de la traza.
+    pub root_cause: Option<String>,
+    /// Mod ids que el reporte senala (Suspected Mods y frames transformados).
+    pub suspected_mods: Vec<String>,
+    pub causes: Vec<CrashCause>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ModMetadataEntry {
     pub file_name: String,
@@ -60,6 +290,9 @@ pub struct ModMetadataEntry {
     pub source: Option<String>,
     #[serde(default)]
     pub kind: Option<String>,
+    /// Version fijada: actualizaciones, modpacks y dependencias automaticas no la reemplazan.
+    #[serde(default)]
+    pub pinned: bool,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -71,9 +304,112 @@ pub struct InstanceCreateRequest {
     pub tags: Option<Vec<String>>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct InstanceDuplicateRequest {
+    pub name: Option<String>,
+    #[serde(default)]
+    pub include_saves: bool,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct InstanceUpdateRequest {
     pub name: Option<String>,
     pub thumbnail: Option<String>,
     pub tags: Option<Vec<String>>,
+    #[serde(default)]
+    pub settings: Option<InstanceSettings>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PlaySession {
+    pub started_at: i64,
+    pub ended_at: i64,
+    pub duration_ms: i64,
+    pub exit_code: Option<i32>,
+    pub crashed: bool,
+    #[serde(default)]
+    pub crash_report: Option<String>,
+    #[serde(default)]
+    pub version: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct InstancePlaytime {
+    pub instance_id: String,
+    pub total_ms: i64,
+    pub session_count: u32,
+    pub crash_count: u32,
+    pub sessions: Vec<PlaySession>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TrashedInstance {
+    pub trash_id: String,
+    pub instance: Instance,
+    pub deleted_at: i64,
+    #[serde(default)]
+    pub size_bytes: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TrashConfig {
+    #[serde(default)]
+    pub max_age_days: Option<u32>,
+    #[serde(default)]
+    pub max_size_mb: Option<u64>,
+}
+
+impl Default for TrashConfig {
+    fn default() -> Self {
+        Self { max_age_days: Some(30), max_size_mb: Some(10 * 1024) }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct InstanceUpgradeRequest {
+    pub mc_version: String,
+    #[serde(default)]
+    pub loader: Option<String>,
+    #[serde(default)]
+    pub loader_version: Option<String>,
+    /// Si es true, los mods sin version compatible se desactivan en lugar de bloquear el cambio.
+    #[serde(default)]
+    pub disable_unavailable: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum UpgradeModStatus {
+    Upgrade,
+    Unchanged,
+    Unavailable,
+    LocalOnly,
+    Pinned,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UpgradeModEntry {
+    pub file_name: String,
+    pub enabled: bool,
+    pub project_id: Option<String>,
+    pub current_version_id: Option<String>,
+    pub target_version_id: Option<String>,
+    pub target_file_name: Option<String>,
+    pub status: UpgradeModStatus,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct InstanceUpgradePreview {
+    pub instance_id: String,
+    pub from_version: String,
+    pub from_loader: String,
+    pub mc_version: String,
+    pub loader: String,
+    pub loader_version: Option<String>,
+    pub mods: Vec<UpgradeModEntry>,
+    pub upgrade_count: u32,
+    pub unavailable_count: u32,
+    pub local_only_count: u32,
+    #[serde(default)]
+    pub pinned_count: u32,
 }
@@ -2,14 +2,18 @@ pub mod auth;
 pub mod curseforge;
 pub mod instances;
 pub mod modrinth;
+pub mod prism;
 pub mod runtime;
 pub mod settings;
+pub mod templates;
 pub mod versions;
 
 pub use auth::*;
 pub use curseforge::*;
 pub use instances::*;
 pub use modrinth::*;
+pub use prism::*;
 pub use runtime::*;
 pub use settings::*;
+pub use templates::*;
 pub use versions::*;
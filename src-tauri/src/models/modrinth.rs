@@ -87,6 +87,10 @@ pub struct ModrinthPackIndex {
     pub files: Vec<ModrinthPackFile>,
     #[serde(default)]
     pub dependencies: HashMap<String, String>,
+    /// Extension propia: rutas (`mods/x.jar`) fijadas en la instancia exportada.
+    /// Otros launchers ignoran el campo.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pinned: Vec<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -105,3 +109,50 @@ pub struct ModrinthPackEnv {
     pub client: Option<String>,
     pub server: Option<String>,
 }
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct IdentifiedContent {
+    pub file_name: String,
+    pub kind: String,
+    pub project_id: Option<String>,
+    pub version_id: String,
+    pub version_name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ContentIdentifyResult {
+    pub scanned: u32,
+    pub matched: Vec<IdentifiedContent>,
+    pub unmatched: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ContentUpdateInfo {
+    pub file_name: String,
+    pub kind: String,
+    pub enabled: bool,
+    pub project_id: Option<String>,
+    pub current_version_id: Option<String>,
+    pub current_version_number: Option<String>,
+    pub latest_version_id: String,
+    pub latest_version_number: String,
+    pub latest_file_name: String,
+    pub changelog_url: String,
+    /// Fijado: se informa la version nueva pero no se aplica.
+    #[serde(default)]
+    pub pinned: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ContentUpdateResult {
+    pub updated: Vec<String>,
+    pub failed: Vec<String>,
+    /// Archivos pedidos que no se tocaron por estar fijados.
+    #[serde(default)]
+    pub pinned: Vec<String>,
+    /// Archivos pedidos sin una actualizacion pendiente (ya al dia o no encontrados).
+    #[serde(default)]
+    pub skipped: Vec<String>,
+    /// Carpeta donde quedaron los archivos reemplazados.
+    pub replaced_dir: Option<String>,
+}
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MmcPack {
+    #[serde(rename = "formatVersion", default = "default_mmc_format_version")]
+    pub format_version: u32,
+    #[serde(default)]
+    pub components: Vec<MmcComponent>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MmcComponent {
+    pub uid: String,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(rename = "cachedName", default, skip_serializing_if = "Option::is_none")]
+    pub cached_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub important: Option<bool>,
+}
+
+fn default_mmc_format_version() -> u32 {
+    1
+}
@@ -23,6 +23,32 @@ pub struct GameProcessPayload {
     pub code: Option<i32>,
 }
 
+/// Juego lanzado por el launcher que sigue en ejecucion.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RunningGame {
+    pub pid: u32,
+    pub instance_id: Option<String>,
+    pub version: String,
+    pub started_at: i64,
+    pub log_path: String,
+}
+
+/// Una linea de salida del juego (evento `game-log`).
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GameLogLine {
+    /// Creciente entre todos los juegos; sirve para pedir solo lo nuevo.
+    pub seq: u64,
+    pub pid: u32,
+    pub instance_id: Option<String>,
+    /// "stdout" o "stderr".
+    pub stream: String,
+    pub level: Option<String>,
+    pub thread: Option<String>,
+    pub logger: Option<String>,
+    pub message: String,
+    pub timestamp: i64,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SkinInfo {
     pub name: String,
@@ -38,3 +64,62 @@ pub struct SystemJava {
     pub path: String,
     pub message: String,
 }
+
+/// Regla de redaccion propia: cada coincidencia de `pattern` (regex) se reemplaza.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RedactionRule {
+    pub name: String,
+    pub pattern: String,
+    /// Texto de reemplazo; por defecto `<redactado>`.
+    #[serde(default)]
+    pub replacement: Option<String>,
+}
+
+/// Que se quita de los reportes de diagnostico antes de comprimirlos.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RedactionConfig {
+    #[serde(default = "default_true")]
+    pub access_tokens: bool,
+    /// Usuario del sistema en rutas y nombre del jugador.
+    #[serde(default = "default_true")]
+    pub usernames: bool,
+    #[serde(default = "default_true")]
+    pub ip_addresses: bool,
+    #[serde(default = "default_true")]
+    pub session_ids: bool,
+    #[serde(default)]
+    pub custom: Vec<RedactionRule>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for RedactionConfig {
+    fn default() -> Self {
+        Self {
+            access_tokens: true,
+            usernames: true,
+            ip_addresses: true,
+            session_ids: true,
+            custom: Vec::new(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RedactedFile {
+    pub path: String,
+    /// Reemplazos por regla.
+    pub counts: std::collections::BTreeMap<String, usize>,
+}
+
+/// Se guarda en el zip como `redaction-manifest.json`; nunca incluye los valores quitados.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct RedactionManifest {
+    pub rules: Vec<String>,
+    pub files: Vec<RedactedFile>,
+    /// Archivos que no son texto y se dejaron fuera del reporte.
+    pub omitted: Vec<String>,
+    pub total: usize,
+}
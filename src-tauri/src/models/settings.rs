@@ -12,6 +12,9 @@ pub struct GameSettings {
     pub java_path: String,
     #[serde(rename = "maxFps", default = "default_max_fps")]
     pub max_fps: u32,
+    /// Cuantos logs de lanzamiento se conservan por instancia.
+    #[serde(rename = "launchLogsKeep", default = "default_launch_logs_keep")]
+    pub launch_logs_keep: u32,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -43,3 +46,58 @@ fn default_memory_max() -> u32 {
 fn default_max_fps() -> u32 {
     120
 }
+
+fn default_launch_logs_keep() -> u32 {
+    10
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct InstanceSettings {
+    #[serde(default)]
+    pub resolution: Option<Resolution>,
+    #[serde(default)]
+    pub memory: Option<MemorySettings>,
+    #[serde(rename = "javaArgs", default)]
+    pub java_args: Option<String>,
+    #[serde(rename = "javaPath", default)]
+    pub java_path: Option<String>,
+    #[serde(rename = "maxFps", default)]
+    pub max_fps: Option<u32>,
+}
+
+impl InstanceSettings {
+    pub fn is_empty(&self) -> bool {
+        self.resolution.is_none()
+            && self.memory.is_none()
+            && self.java_args.is_none()
+            && self.java_path.is_none()
+            && self.max_fps.is_none()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SettingSource {
+    Instance,
+    Global,
+    Default,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SettingsSources {
+    pub resolution: SettingSource,
+    pub fullscreen: SettingSource,
+    pub memory: SettingSource,
+    #[serde(rename = "javaArgs")]
+    pub java_args: SettingSource,
+    #[serde(rename = "javaPath")]
+    pub java_path: SettingSource,
+    #[serde(rename = "maxFps")]
+    pub max_fps: SettingSource,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EffectiveGameSettings {
+    pub settings: GameSettings,
+    pub sources: SettingsSources,
+}
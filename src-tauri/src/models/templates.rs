@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::InstanceSummary;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TemplateProject {
+    pub project_id: String,
+    /// mods, resourcepacks o shaderpacks.
+    #[serde(default = "default_template_kind")]
+    pub kind: String,
+}
+
+fn default_template_kind() -> String {
+    "mods".to_string()
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct InstanceTemplate {
+    pub id: String,
+    pub name: String,
+    pub loader: String,
+    pub mc_version: String,
+    #[serde(default)]
+    pub loader_version: Option<String>,
+    #[serde(default)]
+    pub projects: Vec<TemplateProject>,
+    /// Claves de options.txt que se copian tal cual a la nueva instancia.
+    #[serde(default)]
+    pub options: BTreeMap<String, String>,
+    /// Ruta relativa dentro de `config/` -> contenido del archivo.
+    #[serde(default)]
+    pub config_files: BTreeMap<String, String>,
+    pub created_at: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TemplateSaveRequest {
+    pub name: String,
+    /// Si no se indica se guardan todas las claves de options.txt.
+    #[serde(default)]
+    pub option_keys: Option<Vec<String>>,
+    /// Si no se indica se guardan todos los archivos de texto de `config/`.
+    #[serde(default)]
+    pub config_files: Option<Vec<String>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TemplateCreateRequest {
+    pub name: String,
+    /// Version de Minecraft destino; por defecto la de la plantilla.
+    #[serde(default)]
+    pub mc_version: Option<String>,
+    #[serde(default)]
+    pub loader_version: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TemplateCreateResult {
+    pub instance: InstanceSummary,
+    pub installed: Vec<String>,
+    pub skipped: Vec<String>,
+}
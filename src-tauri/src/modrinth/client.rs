@@ -3,8 +3,14 @@ use crate::error::{AppError, AppResult};
 use crate::models::{ModrinthProject, ModrinthSearchResponse, ModrinthVersion};
 use reqwest::Url;
 use serde::de::DeserializeOwned;
+use std::collections::HashMap;
 use tauri::AppHandle;
 
+// Modrinth limita el tamano del cuerpo; se consulta por lotes.
+const HASH_LOOKUP_CHUNK: usize = 200;
+// Los ids van en la URL; con lotes asi no se pasa del largo que aceptan los servidores.
+const VERSION_IDS_CHUNK: usize = 100;
+
 async fn fetch_json_cached<T: DeserializeOwned>(app: &AppHandle, url: &str) -> AppResult<T> {
     let text = fetch_text_with_cache(app, url, None, false).await?;
     serde_json::from_str::<T>(&text).map_err(|e| {
@@ -115,3 +121,78 @@ pub(super) async fn modrinth_get_version(
     let url = format!("https://api.modrinth.com/v2/version/{}", version_id);
     fetch_json_cached::<ModrinthVersion>(app, &url).await
 }
+
+/// Varias versiones por id con el endpoint `/v2/versions?ids=[...]`.
+pub(super) async fn modrinth_get_versions(
+    app: &AppHandle,
+    version_ids: &[String],
+) -> AppResult<Vec<ModrinthVersion>> {
+    let mut out = Vec::new();
+    for chunk in version_ids.chunks(VERSION_IDS_CHUNK) {
+        let ids = serde_json::to_string(chunk).map_err(|e| AppError::Message(e.to_string()))?;
+        let url = Url::parse_with_params("https://api.modrinth.com/v2/versions", [("ids", ids)])
+            .map_err(|e| AppError::Message(e.to_string()))?;
+        out.extend(fetch_json_cached::<Vec<ModrinthVersion>>(app, url.as_str()).await?);
+    }
+    Ok(out)
+}
+
+async fn post_hash_lookup(
+    url: &str,
+    hashes: &[String],
+    extra: serde_json::Value,
+) -> AppResult<HashMap<String, ModrinthVersion>> {
+    let client = crate::utils::create_client();
+    let mut out = HashMap::new();
+    for chunk in hashes.chunks(HASH_LOOKUP_CHUNK) {
+        let mut body = extra.clone();
+        body["hashes"] = serde_json::json!(chunk);
+        let res = client
+            .post(url)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| AppError::Message(e.to_string()))?;
+        if !res.status().is_success() {
+            return Err(format!("Modrinth respondio {} al buscar hashes", res.status()).into());
+        }
+        let parsed = res
+            .json::<HashMap<String, ModrinthVersion>>()
+            .await
+            .map_err(|e| AppError::Message(e.to_string()))?;
+        out.extend(parsed);
+    }
+    Ok(out)
+}
+
+/// Busca versiones por hash de archivo (`algorithm` = sha1 o sha512). La respuesta no se cachea.
+pub(crate) async fn modrinth_versions_by_hash(
+    hashes: &[String],
+    algorithm: &str,
+) -> AppResult<HashMap<String, ModrinthVersion>> {
+    post_hash_lookup(
+        "https://api.modrinth.com/v2/version_files",
+        hashes,
+        serde_json::json!({ "algorithm": algorithm }),
+    )
+    .await
+}
+
+/// Ultima version compatible para cada hash, filtrada por loaders y versiones del juego.
+pub(crate) async fn modrinth_latest_versions_by_hash(
+    hashes: &[String],
+    algorithm: &str,
+    loaders: &[String],
+    game_versions: &[String],
+) -> AppResult<HashMap<String, ModrinthVersion>> {
+    post_hash_lookup(
+        "https://api.modrinth.com/v2/version_files/update",
+        hashes,
+        serde_json::json!({
+            "algorithm": algorithm,
+            "loaders": loaders,
+            "game_versions": game_versions,
+        }),
+    )
+    .await
+}
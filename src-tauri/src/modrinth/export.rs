@@ -1,4 +1,6 @@
+use crate::content::{load_mods_metadata, pinned_paths};
 use crate::error::{AppError, AppResult};
+use crate::instances::is_dir_link;
 use crate::models::Instance;
 use crate::utils::append_action_log;
 use serde_json::json;
@@ -37,7 +39,7 @@ fn resolve_export_path(
     Ok(export_dir.join(format!("{}_{}.mrpack", safe_name, ts)))
 }
 
-fn sanitize_pack_name(name: &str) -> String {
+pub(crate) fn sanitize_pack_name(name: &str) -> String {
     let trimmed = name.trim();
     if trimmed.is_empty() {
         return "modpack".to_string();
@@ -71,18 +73,16 @@ fn extract_base_version(version_id: &str) -> String {
     version_id.to_string()
 }
 
-fn parse_loader_version(inst: &Instance) -> (String, HashMap<String, String>) {
+pub(crate) fn parse_loader_version(inst: &Instance) -> (String, HashMap<String, String>) {
     let mut deps = HashMap::new();
     let mc_version = extract_base_version(&inst.version);
     deps.insert("minecraft".to_string(), mc_version.clone());
     match inst.loader.as_str() {
-        "fabric" => {
-            if inst.version.starts_with("fabric-loader-") {
-                let parts: Vec<&str> = inst.version.split('-').collect();
-                if parts.len() >= 3 {
-                    let loader_version = parts[2].to_string();
-                    deps.insert("fabric-loader".to_string(), loader_version);
-                }
+        "fabric" if inst.version.starts_with("fabric-loader-") => {
+            let parts: Vec<&str> = inst.version.split('-').collect();
+            if parts.len() >= 3 {
+                let loader_version = parts[2].to_string();
+                deps.insert("fabric-loader".to_string(), loader_version);
             }
         }
         "forge" => {
@@ -112,10 +112,11 @@ fn should_skip_override(rel: &Path) -> bool {
     )
 }
 
-fn add_overrides_to_zip(
+pub(crate) fn add_overrides_to_zip(
     zip: &mut zip::ZipWriter<std::fs::File>,
     base: &Path,
     dir: &Path,
+    prefix: &str,
     options: FileOptions,
 ) -> AppResult<()> {
     for entry in std::fs::read_dir(dir).map_err(|e| AppError::Message(e.to_string()))? {
@@ -128,15 +129,19 @@ fn add_overrides_to_zip(
         if should_skip_override(rel) {
             continue;
         }
+        // Las carpetas compartidas son de todas las instancias; no forman parte del export.
+        if is_dir_link(&path) {
+            continue;
+        }
         if path.is_dir() {
-            add_overrides_to_zip(zip, base, &path, options)?;
+            add_overrides_to_zip(zip, base, &path, prefix, options)?;
             continue;
         }
         if rel.file_name().and_then(|s| s.to_str()) == Some("mods.cache.json") {
             continue;
         }
         let rel_name = rel.to_string_lossy().replace('\\', "/");
-        let zip_name = format!("overrides/{}", rel_name);
+        let zip_name = format!("{}/{}", prefix, rel_name);
         zip.start_file(zip_name, options).map_err(|e| AppError::Message(e.to_string()))?;
         let mut f = std::fs::File::open(&path).map_err(|e| AppError::Message(e.to_string()))?;
         std::io::copy(&mut f, zip).map_err(|e| AppError::Message(e.to_string()))?;
@@ -156,7 +161,7 @@ pub async fn export_modpack_mrpack_impl(
     }
 
     let (_mc_version, deps) = parse_loader_version(&inst);
-    let index_json = json!({
+    let mut index_json = json!({
         "formatVersion": 1,
         "game": "minecraft",
         "versionId": inst.id,
@@ -165,6 +170,11 @@ pub async fn export_modpack_mrpack_impl(
         "files": [],
         "dependencies": deps,
     });
+    // Los fijados viajan en el indice para recuperarlos al importar (ver ModrinthPackIndex).
+    let pinned = pinned_paths(&load_mods_metadata(app, &instance_id).await);
+    if !pinned.is_empty() {
+        index_json["pinned"] = json!(pinned);
+    }
 
     let app_handle = app.clone();
     let inst_clone = inst.clone();
@@ -187,7 +197,13 @@ pub async fn export_modpack_mrpack_impl(
         std::io::Write::write_all(&mut zip, &index_raw)
             .map_err(|e| AppError::Message(e.to_string()))?;
 
-        add_overrides_to_zip(&mut zip, &instance_dir_clone, &instance_dir_clone, options)?;
+        add_overrides_to_zip(
+            &mut zip,
+            &instance_dir_clone,
+            &instance_dir_clone,
+            "overrides",
+            options,
+        )?;
 
         zip.finish().map_err(|e| AppError::Message(e.to_string()))?;
         Ok(zip_path)
@@ -0,0 +1,159 @@
+use crate::content::{
+    load_mods_metadata, metadata_kind_matches, strip_disabled, upsert_mod_metadata,
+};
+use crate::error::{AppError, AppResult};
+use crate::models::{ContentIdentifyResult, IdentifiedContent, ModMetadataEntry};
+use crate::utils::append_action_log;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+use super::client::modrinth_versions_by_hash;
+use super::shared::{instance_dir, sha1_file, CONTENT_KINDS};
+
+fn is_content_file(kind: &str, name: &str) -> bool {
+    let (clean, _) = strip_disabled(name);
+    let lower = clean.to_lowercase();
+    match kind {
+        "mods" => lower.ends_with(".jar"),
+        _ => lower.ends_with(".zip"),
+    }
+}
+
+// (kind, nombre en disco, nombre sin .disabled, ruta)
+fn collect_unknown_files(
+    base: &Path,
+    kinds: &[&str],
+    metadata: &[ModMetadataEntry],
+) -> Vec<(String, String, String, PathBuf)> {
+    let mut out = Vec::new();
+    for kind in kinds {
+        let entries = match std::fs::read_dir(base.join(kind)) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !path.is_file() {
+                continue;
+            }
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !is_content_file(kind, &name) {
+                continue;
+            }
+            let (clean, _) = strip_disabled(&name);
+            let known = metadata.iter().any(|m| {
+                m.project_id.is_some()
+                    && m.file_name == clean
+                    && metadata_kind_matches(&m.kind, kind)
+            });
+            if !known {
+                out.push((kind.to_string(), name, clean, path));
+            }
+        }
+    }
+    out
+}
+
+/// Identifica por hash el contenido agregado a mano y guarda lo encontrado en mods.json.
+pub async fn identify_instance_content_impl(
+    app: &AppHandle,
+    instance_id: String,
+    kind: Option<String>,
+) -> AppResult<ContentIdentifyResult> {
+    let base = instance_dir(app, &instance_id);
+    if !base.exists() {
+        return Err("La instancia no existe".to_string().into());
+    }
+    let kinds: Vec<&str> = match kind.as_deref() {
+        Some(k) => match CONTENT_KINDS.iter().find(|c| **c == k) {
+            Some(c) => vec![*c],
+            None => {
+                return Err("Tipo inválido (mods/resourcepacks/shaderpacks)".to_string().into())
+            }
+        },
+        None => CONTENT_KINDS.to_vec(),
+    };
+
+    let metadata = load_mods_metadata(app, &instance_id).await;
+    let kinds_owned: Vec<String> = kinds.iter().map(|k| k.to_string()).collect();
+    let hashed = tokio::task::spawn_blocking(move || {
+        let kinds: Vec<&str> = kinds_owned.iter().map(|k| k.as_str()).collect();
+        collect_unknown_files(&base, &kinds, &metadata)
+            .into_iter()
+            .filter_map(|(kind, name, clean, path)| {
+                sha1_file(&path).map(|hash| (hash, (kind, name, clean)))
+            })
+            .collect::<Vec<_>>()
+    })
+    .await
+    .map_err(|e| AppError::Message(e.to_string()))?;
+
+    let scanned = hashed.len() as u32;
+    if hashed.is_empty() {
+        return Ok(ContentIdentifyResult { scanned, matched: Vec::new(), unmatched: Vec::new() });
+    }
+    // Archivos identicos (p. ej. el mismo jar activo y `.disabled`) comparten hash.
+    let mut by_hash: HashMap<String, Vec<(String, String, String)>> = HashMap::new();
+    for (hash, file) in hashed {
+        by_hash.entry(hash).or_default().push(file);
+    }
+    let hashes: Vec<String> = by_hash.keys().cloned().collect();
+    let found = modrinth_versions_by_hash(&hashes, "sha1").await?;
+
+    let mut matched = Vec::new();
+    let mut unmatched = Vec::new();
+    for (hash, (kind, name, clean)) in by_hash
+        .into_iter()
+        .flat_map(|(hash, files)| files.into_iter().map(move |f| (hash.clone(), f)))
+    {
+        let version = match found.get(&hash) {
+            Some(v) => v,
+            None => {
+                unmatched.push(name);
+                continue;
+            }
+        };
+        let dependencies = if kind == "mods" {
+            version
+                .dependencies
+                .iter()
+                .filter(|d| d.dependency_type == "required")
+                .filter_map(|d| d.version_id.clone().or_else(|| d.project_id.clone()))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let entry = ModMetadataEntry {
+            file_name: clean.clone(),
+            version_id: Some(version.id.clone()),
+            project_id: version.project_id.clone(),
+            dependencies,
+            source: Some("modrinth".to_string()),
+            kind: Some(kind.clone()),
+            pinned: false,
+        };
+        upsert_mod_metadata(app, &instance_id, entry).await?;
+        matched.push(IdentifiedContent {
+            file_name: clean,
+            kind,
+            project_id: version.project_id.clone(),
+            version_id: version.id.clone(),
+            version_name: version.name.clone(),
+        });
+    }
+    matched.sort_by(|a, b| a.file_name.cmp(&b.file_name));
+    unmatched.sort();
+
+    let _ = append_action_log(
+        app,
+        &format!(
+            "content_identify instance={} scanned={} matched={}",
+            instance_id,
+            scanned,
+            matched.len()
+        ),
+    )
+    .await;
+    Ok(ContentIdentifyResult { scanned, matched, unmatched })
+}
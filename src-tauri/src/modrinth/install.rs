@@ -1,4 +1,4 @@
-use crate::content::upsert_mod_metadata;
+use crate::content::{find_pinned, load_mods_metadata, upsert_mod_metadata};
 use crate::error::{AppError, AppResult};
 use crate::models::{ModMetadataEntry, ModrinthVersion};
 use crate::utils::append_action_log;
@@ -26,13 +26,25 @@ async fn get_version_cached(
     Ok(v)
 }
 
+pub(super) fn pinned_note(skipped: &[String]) -> String {
+    if skipped.is_empty() {
+        String::new()
+    } else {
+        format!(". Fijados sin cambios: {}", skipped.join(", "))
+    }
+}
+
+/// Instala la version y sus dependencias requeridas. Devuelve cuantos archivos se descargaron
+/// y los archivos fijados que se dejaron como estaban.
 pub(super) async fn install_version_with_deps(
     app: &AppHandle,
     instance_id: &str,
     root_version_id: &str,
     loader: Option<&str>,
     game_version: Option<&str>,
-) -> AppResult<usize> {
+) -> AppResult<(usize, Vec<String>)> {
+    let metadata = load_mods_metadata(app, instance_id).await;
+    let mut skipped: Vec<String> = Vec::new();
     let mut visited: HashSet<String> = HashSet::new();
     let mut cache: HashMap<String, ModrinthVersion> = HashMap::new();
     let mut stack: Vec<(String, bool)> = vec![(root_version_id.to_string(), false)];
@@ -63,6 +75,7 @@ pub(super) async fn install_version_with_deps(
                     dependencies,
                     source: Some("modrinth".to_string()),
                     kind: Some("mods".to_string()),
+                    pinned: false,
                 };
                 let _ = upsert_mod_metadata(app, instance_id, entry).await;
             }
@@ -75,6 +88,16 @@ pub(super) async fn install_version_with_deps(
         visited.insert(version_id.clone());
 
         let version = get_version_cached(app, &mut cache, &version_id).await?;
+        let file_name = pick_primary_file(&version).map(|(_, name, _, _)| name).unwrap_or("");
+        if let Some(pinned) =
+            find_pinned(&metadata, "mods", version.project_id.as_deref(), file_name)
+        {
+            // Lo fijado se queda igual y sus dependencias ya estaban resueltas.
+            if !skipped.contains(&pinned.file_name) {
+                skipped.push(pinned.file_name.clone());
+            }
+            continue;
+        }
         stack.push((version_id.clone(), true));
 
         for dep in version.dependencies.clone() {
@@ -104,7 +127,14 @@ pub(super) async fn install_version_with_deps(
         }
     }
 
-    Ok(installed)
+    if !skipped.is_empty() {
+        let _ = append_action_log(
+            app,
+            &format!("install_skip_pinned instance={} files={}", instance_id, skipped.join(",")),
+        )
+        .await;
+    }
+    Ok((installed, skipped))
 }
 
 async fn install_simple_pack(
@@ -145,12 +175,22 @@ pub async fn modrinth_install_version_impl(
     }
     if project_type == "modpack" {
         let version = modrinth_get_version(app, &version_id).await?;
-        let installed = install_modpack(app, &instance_id, &version, loader.as_deref()).await?;
-        return Ok(format!("Modpack instalado ({} archivos)", installed));
+        let (installed, skipped) =
+            install_modpack(app, &instance_id, &version, loader.as_deref()).await?;
+        return Ok(format!("Modpack instalado ({} archivos){}", installed, pinned_note(&skipped)));
     }
     if project_type == "resourcepack" {
         let version = modrinth_get_version(app, &version_id).await?;
         let file_name = pick_primary_file(&version).map(|(_, filename, _, _)| filename.to_string());
+        let metadata = load_mods_metadata(app, &instance_id).await;
+        if let Some(pinned) = find_pinned(
+            &metadata,
+            "resourcepacks",
+            version.project_id.as_deref(),
+            file_name.as_deref().unwrap_or(""),
+        ) {
+            return Ok(format!("{} esta fijado; no se reemplazo", pinned.file_name));
+        }
         let installed = install_simple_pack(app, &instance_id, &version, "resourcepack").await?;
         if let Some(file_name) = file_name {
             let entry = ModMetadataEntry {
@@ -160,6 +200,7 @@ pub async fn modrinth_install_version_impl(
                 dependencies: Vec::new(),
                 source: Some("modrinth".to_string()),
                 kind: Some("resourcepacks".to_string()),
+                pinned: false,
             };
             let _ = upsert_mod_metadata(app, &instance_id, entry).await;
         }
@@ -173,6 +214,15 @@ pub async fn modrinth_install_version_impl(
     if project_type == "shader" {
         let version = modrinth_get_version(app, &version_id).await?;
         let file_name = pick_primary_file(&version).map(|(_, filename, _, _)| filename.to_string());
+        let metadata = load_mods_metadata(app, &instance_id).await;
+        if let Some(pinned) = find_pinned(
+            &metadata,
+            "shaderpacks",
+            version.project_id.as_deref(),
+            file_name.as_deref().unwrap_or(""),
+        ) {
+            return Ok(format!("{} esta fijado; no se reemplazo", pinned.file_name));
+        }
         let installed = install_simple_pack(app, &instance_id, &version, "shader").await?;
         if let Some(file_name) = file_name {
             let entry = ModMetadataEntry {
@@ -182,6 +232,7 @@ pub async fn modrinth_install_version_impl(
                 dependencies: Vec::new(),
                 source: Some("modrinth".to_string()),
                 kind: Some("shaderpacks".to_string()),
+                pinned: false,
             };
             let _ = upsert_mod_metadata(app, &instance_id, entry).await;
         }
@@ -193,7 +244,7 @@ pub async fn modrinth_install_version_impl(
         return Ok(format!("Shader instalado ({} archivo)", installed));
     }
 
-    let installed = install_version_with_deps(
+    let (installed, skipped) = install_version_with_deps(
         app,
         &instance_id,
         &version_id,
@@ -207,5 +258,5 @@ pub async fn modrinth_install_version_impl(
         &format!("mod_install instance={} version={}", instance_id, version_id),
     )
     .await;
-    Ok(format!("Instalados {} mods/dependencias", installed))
+    Ok(format!("Instalados {} mods/dependencias{}", installed, pinned_note(&skipped)))
 }
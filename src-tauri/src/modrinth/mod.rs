@@ -1,9 +1,11 @@
 pub mod client;
 pub mod datapacks;
 pub mod export;
+pub mod identify;
 pub mod install;
 pub mod modpacks;
 pub mod optimization;
+pub mod updates;
 
 mod pack;
 mod shared;
@@ -11,6 +13,8 @@ mod shared;
 pub use client::{modrinth_get_project_impl, modrinth_list_versions_impl, modrinth_search_impl};
 pub use datapacks::modrinth_install_datapack_impl;
 pub use export::export_modpack_mrpack_impl;
+pub use identify::identify_instance_content_impl;
 pub use install::modrinth_install_version_impl;
 pub use modpacks::{import_modpack_mrpack_impl, modrinth_install_modpack_impl};
 pub use optimization::apply_optimization_pack_impl;
+pub use updates::{apply_content_updates_impl, check_content_updates_impl};
@@ -6,7 +6,7 @@ use tauri::AppHandle;
 use tokio::fs as tokio_fs;
 
 use super::client::modrinth_list_versions_impl;
-use super::install::install_version_with_deps;
+use super::install::{install_version_with_deps, pinned_note};
 use super::shared::{instance_dir, instance_mods_dir, pick_primary_file};
 
 fn optimization_mods(loader: &str, game_version: &str) -> Vec<&'static str> {
@@ -119,6 +119,7 @@ pub async fn apply_optimization_pack_impl(
     let mut installed_files: Vec<String> = Vec::new();
     let mut render_mod_used: Option<String> = None;
     let mut missing_projects: Vec<String> = Vec::new();
+    let mut pinned_skipped: Vec<String> = Vec::new();
 
     if (loader == "forge" || loader == "neoforge") && !forge_render_installed(&installed_projects) {
         let mut selected = None;
@@ -132,7 +133,7 @@ pub async fn apply_optimization_pack_impl(
             .await?;
             if let Some(version) = versions.first() {
                 selected = Some(candidate);
-                let count = install_version_with_deps(
+                let (count, skipped) = install_version_with_deps(
                     app,
                     &instance_id,
                     &version.id,
@@ -141,6 +142,7 @@ pub async fn apply_optimization_pack_impl(
                 )
                 .await?;
                 installed += count;
+                pinned_skipped.extend(skipped);
                 if let Some((_, filename, _, _)) = pick_primary_file(version) {
                     installed_files.push(filename.to_string());
                 }
@@ -173,7 +175,7 @@ pub async fn apply_optimization_pack_impl(
         )
         .await?;
         if let Some(version) = versions.first() {
-            let count = install_version_with_deps(
+            let (count, skipped) = install_version_with_deps(
                 app,
                 &instance_id,
                 &version.id,
@@ -182,6 +184,7 @@ pub async fn apply_optimization_pack_impl(
             )
             .await?;
             installed += count;
+            pinned_skipped.extend(skipped);
             if let Some((_, filename, _, _)) = pick_primary_file(version) {
                 installed_files.push(filename.to_string());
             }
@@ -222,5 +225,9 @@ pub async fn apply_optimization_pack_impl(
     )
     .await;
 
-    Ok(format!("Optimizacion aplicada: {} mods instalados", installed))
+    Ok(format!(
+        "Optimizacion aplicada: {} mods instalados{}",
+        installed,
+        pinned_note(&pinned_skipped)
+    ))
 }
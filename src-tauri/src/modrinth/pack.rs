@@ -1,8 +1,13 @@
+use crate::content::{
+    find_pinned, load_mods_metadata, pinned_mod_ids, pinned_paths, read_mod_info,
+    set_content_pinned_impl,
+};
 use crate::downloader::download_file_checked;
 use crate::error::{AppError, AppResult};
 use crate::models::{ModrinthPackIndex, ModrinthVersion, ProgressPayload};
 use crate::utils::get_launcher_dir;
 use futures_util::{stream, StreamExt};
+use std::collections::{HashMap, HashSet};
 use std::io::Read;
 use std::path::{Path, PathBuf};
 use tauri::{AppHandle, Emitter};
@@ -39,10 +44,31 @@ pub(super) fn zip_read_index(pack_path: &Path) -> AppResult<ModrinthPackIndex> {
     Err("modrinth.index.json no encontrado".to_string().into())
 }
 
-fn zip_extract_overrides(pack_path: &Path, instance_base: &Path) -> AppResult<ModrinthPackIndex> {
+// Las URLs del CDN tienen la forma `.../data/<project_id>/versions/<version_id>/<archivo>`.
+fn project_id_from_url(url: &str) -> Option<&str> {
+    let rest = url.split("/data/").nth(1)?;
+    let (project, tail) = rest.split_once('/')?;
+    tail.starts_with("versions/").then_some(project)
+}
+
+// Jars de mods que trae el pack como override: pueden ser un mod fijado con otro nombre.
+fn is_mod_jar(rel: &str) -> bool {
+    let rel = rel.to_ascii_lowercase();
+    rel.starts_with("mods/") && (rel.ends_with(".jar") || rel.ends_with(".jar.disabled"))
+}
+
+/// Extrae los overrides salvo los fijados. Devuelve el indice del pack y los archivos fijados
+/// que se conservaron porque un override traia el mismo mod.
+fn zip_extract_overrides(
+    pack_path: &Path,
+    instance_base: &Path,
+    pinned: &HashSet<String>,
+    pinned_ids: &HashMap<String, String>,
+) -> AppResult<(ModrinthPackIndex, Vec<String>)> {
     let file = std::fs::File::open(pack_path).map_err(|e| AppError::Message(e.to_string()))?;
     let mut archive = ZipArchive::new(file).map_err(|e| AppError::Message(e.to_string()))?;
     let mut index_raw = None;
+    let mut skipped: Vec<String> = Vec::new();
 
     for i in 0..archive.len() {
         let mut entry = archive.by_index(i).map_err(|e| AppError::Message(e.to_string()))?;
@@ -65,23 +91,52 @@ fn zip_extract_overrides(pack_path: &Path, instance_base: &Path) -> AppResult<Mo
         if rel.is_empty() || rel.contains("..") || rel.starts_with('/') || rel.starts_with('\\') {
             continue;
         }
+        if pinned.contains(rel) || pinned.contains(rel.trim_end_matches(".disabled")) {
+            continue;
+        }
 
         let dest = instance_base.join(rel);
         if entry.is_dir() {
             std::fs::create_dir_all(&dest).map_err(|e| AppError::Message(e.to_string()))?;
-        } else {
-            if let Some(parent) = dest.parent() {
-                std::fs::create_dir_all(parent).map_err(|e| AppError::Message(e.to_string()))?;
-            }
+            continue;
+        }
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| AppError::Message(e.to_string()))?;
+        }
+        if pinned_ids.is_empty() || !is_mod_jar(rel) {
             let mut out =
                 std::fs::File::create(&dest).map_err(|e| AppError::Message(e.to_string()))?;
             std::io::copy(&mut entry, &mut out).map_err(|e| AppError::Message(e.to_string()))?;
+            continue;
+        }
+
+        // Se lee el id del mod antes de dejarlo en su lugar.
+        let mut tmp_name = dest.as_os_str().to_owned();
+        tmp_name.push(".part");
+        let tmp = PathBuf::from(tmp_name);
+        let written =
+            std::fs::File::create(&tmp).and_then(|mut out| std::io::copy(&mut entry, &mut out));
+        if let Err(e) = written {
+            let _ = std::fs::remove_file(&tmp);
+            return Err(AppError::Message(e.to_string()));
+        }
+        let kept = read_mod_info(&tmp).and_then(|info| pinned_ids.get(&info.mod_id));
+        if let Some(file_name) = kept {
+            let _ = std::fs::remove_file(&tmp);
+            if !skipped.contains(file_name) {
+                skipped.push(file_name.clone());
+            }
+            continue;
+        }
+        if let Err(e) = std::fs::rename(&tmp, &dest) {
+            let _ = std::fs::remove_file(&tmp);
+            return Err(AppError::Message(e.to_string()));
         }
     }
 
     let raw = index_raw
         .ok_or_else(|| AppError::Message("modrinth.index.json no encontrado".to_string()))?;
-    parse_pack_index(&raw)
+    Ok((parse_pack_index(&raw)?, skipped))
 }
 
 pub(super) async fn download_modpack_file(
@@ -106,20 +161,27 @@ pub(super) async fn download_modpack_file(
     Ok(pack_path)
 }
 
+/// Instala los archivos del modpack sin tocar los fijados. Devuelve cuantos archivos se
+/// descargaron y los fijados que se dejaron como estaban.
 pub(super) async fn install_modpack_from_pack(
     app: &AppHandle,
     instance_id: &str,
     pack_path: &Path,
-) -> AppResult<usize> {
+) -> AppResult<(usize, Vec<String>)> {
     let base = instance_dir(app, instance_id);
     tokio_fs::create_dir_all(&base).await.map_err(|e| AppError::Message(e.to_string()))?;
 
+    let metadata = load_mods_metadata(app, instance_id).await;
+    let pinned: HashSet<String> = pinned_paths(&metadata).into_iter().collect();
     let base_clone = base.clone();
     let pack_path_clone = pack_path.to_path_buf();
-    let index =
-        tokio::task::spawn_blocking(move || zip_extract_overrides(&pack_path_clone, &base_clone))
-            .await
-            .map_err(|e| AppError::Message(e.to_string()))??;
+    let pinned_metadata = metadata.clone();
+    let (index, mut skipped) = tokio::task::spawn_blocking(move || {
+        let pinned_ids = pinned_mod_ids(&base_clone.join("mods"), &pinned_metadata);
+        zip_extract_overrides(&pack_path_clone, &base_clone, &pinned, &pinned_ids)
+    })
+    .await
+    .map_err(|e| AppError::Message(e.to_string()))??;
 
     let mut specs: Vec<(String, PathBuf, u64, Option<String>)> = Vec::new();
     for entry in index.files {
@@ -137,6 +199,15 @@ pub(super) async fn install_modpack_from_pack(
         if entry.downloads.is_empty() {
             continue;
         }
+        if let Some((kind, file_name)) = entry.path.split_once('/') {
+            let project_id = project_id_from_url(&entry.downloads[0]);
+            if let Some(pinned) = find_pinned(&metadata, kind, project_id, file_name) {
+                if !skipped.contains(&pinned.file_name) {
+                    skipped.push(pinned.file_name.clone());
+                }
+                continue;
+            }
+        }
         let url = entry.downloads[0].clone();
         let sha1 = entry.hashes.get("sha1").map(|s| s.to_string());
         let dest = base.join(&entry.path);
@@ -144,11 +215,12 @@ pub(super) async fn install_modpack_from_pack(
     }
 
     if specs.is_empty() {
+        apply_pack_pins(app, instance_id, &index.pinned).await;
         let _ = app.emit(
             "download-progress",
             ProgressPayload { task: "Modpack listo".to_string(), percent: 100.0 },
         );
-        return Ok(0);
+        return Ok((0, skipped));
     }
 
     let total = specs.len();
@@ -176,11 +248,21 @@ pub(super) async fn install_modpack_from_pack(
         );
     }
 
+    apply_pack_pins(app, instance_id, &index.pinned).await;
     let _ = app.emit(
         "download-progress",
         ProgressPayload { task: "Modpack listo".to_string(), percent: 100.0 },
     );
-    Ok(installed)
+    Ok((installed, skipped))
+}
+
+// Recupera los fijados que trae un .mrpack exportado por el launcher.
+async fn apply_pack_pins(app: &AppHandle, instance_id: &str, paths: &[String]) {
+    for path in paths {
+        if let Some((kind, file_name)) = path.split_once('/') {
+            let _ = set_content_pinned_impl(app, instance_id, kind, file_name, true).await;
+        }
+    }
 }
 
 pub(super) async fn install_modpack(
@@ -188,7 +270,7 @@ pub(super) async fn install_modpack(
     instance_id: &str,
     version: &ModrinthVersion,
     loader: Option<&str>,
-) -> AppResult<usize> {
+) -> AppResult<(usize, Vec<String>)> {
     let pack_path = download_modpack_file(app, version).await?;
     let pack_path_clone = pack_path.clone();
     let index = tokio::task::spawn_blocking(move || zip_read_index(&pack_path_clone))
@@ -1,8 +1,12 @@
 use crate::models::ModrinthVersion;
 use crate::utils::get_launcher_dir;
-use std::path::PathBuf;
+use sha1::{Digest, Sha1};
+use std::io::Read;
+use std::path::{Path, PathBuf};
 use tauri::AppHandle;
 
+pub(super) const CONTENT_KINDS: [&str; 3] = ["mods", "resourcepacks", "shaderpacks"];
+
 pub(super) fn instance_dir(app: &AppHandle, instance_id: &str) -> PathBuf {
     get_launcher_dir(app).join("instances").join(instance_id)
 }
@@ -33,3 +37,17 @@ pub(super) fn pick_primary_file(
     let sha1 = file.hashes.get("sha1").map(|s| s.as_str());
     Some((file.url.as_str(), file.filename.as_str(), file.size, sha1))
 }
+
+pub(super) fn sha1_file(path: &Path) -> Option<String> {
+    let mut file = std::fs::File::open(path).ok()?;
+    let mut hasher = Sha1::new();
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf).ok()?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Some(format!("{:x}", hasher.finalize()))
+}
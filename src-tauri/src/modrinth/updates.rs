@@ -0,0 +1,310 @@
+use crate::content::{load_mods_metadata, metadata_kind_matches, save_mods_metadata};
+use crate::error::{AppError, AppResult};
+use crate::instances::{get_instance_impl, refresh_instance_mods_cache};
+use crate::models::{ContentUpdateInfo, ContentUpdateResult, ModMetadataEntry, ModrinthVersion};
+use crate::utils::append_action_log;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tauri::AppHandle;
+use tokio::fs as tokio_fs;
+
+use super::client::{modrinth_get_versions, modrinth_latest_versions_by_hash};
+use super::export::parse_loader_version;
+use super::shared::{instance_dir, pick_primary_file, sha1_file, CONTENT_KINDS};
+
+// Modrinth publica resource packs y shaders con sus propios "loaders".
+fn loaders_for_kind(kind: &str, instance_loader: &str) -> Vec<String> {
+    match kind {
+        "resourcepacks" => vec!["minecraft".to_string()],
+        "shaderpacks" => ["iris", "optifine", "canvas", "vanilla"].map(String::from).to_vec(),
+        _ => match instance_loader {
+            "fabric" | "forge" | "neoforge" => vec![instance_loader.to_string()],
+            _ => Vec::new(),
+        },
+    }
+}
+
+struct TrackedFile {
+    kind: String,
+    clean_name: String,
+    enabled: bool,
+    path: PathBuf,
+    project_id: Option<String>,
+    version_id: Option<String>,
+    pinned: bool,
+}
+
+fn find_tracked_files(base: &std::path::Path, metadata: &[ModMetadataEntry]) -> Vec<TrackedFile> {
+    let mut out = Vec::new();
+    for entry in metadata {
+        if entry.project_id.is_none() || entry.version_id.is_none() {
+            continue;
+        }
+        let kind = match CONTENT_KINDS.iter().find(|k| metadata_kind_matches(&entry.kind, k)) {
+            Some(kind) => kind.to_string(),
+            None => continue,
+        };
+        let dir = base.join(&kind);
+        let enabled_path = dir.join(&entry.file_name);
+        let disabled_path = dir.join(format!("{}.disabled", entry.file_name));
+        let (path, enabled) = if enabled_path.is_file() {
+            (enabled_path, true)
+        } else if disabled_path.is_file() {
+            (disabled_path, false)
+        } else {
+            continue;
+        };
+        out.push(TrackedFile {
+            kind,
+            clean_name: entry.file_name.clone(),
+            enabled,
+            path,
+            project_id: entry.project_id.clone(),
+            version_id: entry.version_id.clone(),
+            pinned: entry.pinned,
+        });
+    }
+    out
+}
+
+async fn find_updates(
+    app: &AppHandle,
+    instance_id: &str,
+    kind: Option<&str>,
+) -> AppResult<Vec<(ContentUpdateInfo, ModrinthVersion)>> {
+    let inst = get_instance_impl(app, instance_id).await?;
+    let (mc_version, _) = parse_loader_version(&inst);
+    let base = instance_dir(app, instance_id);
+    let metadata = load_mods_metadata(app, instance_id).await;
+
+    let hashed = tokio::task::spawn_blocking(move || {
+        find_tracked_files(&base, &metadata)
+            .into_iter()
+            .filter_map(|f| sha1_file(&f.path).map(|hash| (hash, f)))
+            .collect::<Vec<_>>()
+    })
+    .await
+    .map_err(|e| AppError::Message(e.to_string()))?;
+
+    let mut outdated: Vec<(&TrackedFile, ModrinthVersion, String)> = Vec::new();
+    for target_kind in CONTENT_KINDS {
+        if kind.map(|k| k != target_kind).unwrap_or(false) {
+            continue;
+        }
+        let loaders = loaders_for_kind(target_kind, &inst.loader);
+        let files: Vec<&(String, TrackedFile)> =
+            hashed.iter().filter(|(_, f)| f.kind == target_kind).collect();
+        if files.is_empty() || loaders.is_empty() {
+            continue;
+        }
+        let hashes: Vec<String> = files.iter().map(|(h, _)| h.clone()).collect();
+        let latest = modrinth_latest_versions_by_hash(
+            &hashes,
+            "sha1",
+            &loaders,
+            std::slice::from_ref(&mc_version),
+        )
+        .await?;
+        for (hash, file) in files {
+            let version = match latest.get(hash) {
+                Some(v) if Some(&v.id) != file.version_id.as_ref() => v,
+                _ => continue,
+            };
+            let latest_file_name = match pick_primary_file(version) {
+                Some((_, name, _, _)) => name.to_string(),
+                None => continue,
+            };
+            outdated.push((file, version.clone(), latest_file_name));
+        }
+    }
+
+    // El numero de la version instalada es solo informativo: si la consulta falla queda vacio.
+    let mut current_ids: Vec<String> =
+        outdated.iter().filter_map(|(file, _, _)| file.version_id.clone()).collect();
+    current_ids.sort();
+    current_ids.dedup();
+    let current_numbers: HashMap<String, String> = if current_ids.is_empty() {
+        HashMap::new()
+    } else {
+        modrinth_get_versions(app, &current_ids)
+            .await
+            .map(|versions| versions.into_iter().map(|v| (v.id, v.version_number)).collect())
+            .unwrap_or_default()
+    };
+
+    let mut out = Vec::new();
+    for (file, version, latest_file_name) in outdated {
+        let current_version_number =
+            file.version_id.as_ref().and_then(|id| current_numbers.get(id)).cloned();
+        let project = file.project_id.clone().or_else(|| version.project_id.clone());
+        let info = ContentUpdateInfo {
+            file_name: file.clean_name.clone(),
+            kind: file.kind.clone(),
+            enabled: file.enabled,
+            project_id: project.clone(),
+            current_version_id: file.version_id.clone(),
+            current_version_number,
+            latest_version_id: version.id.clone(),
+            latest_version_number: version.version_number.clone(),
+            latest_file_name,
+            changelog_url: format!(
+                "https://modrinth.com/project/{}/version/{}",
+                project.unwrap_or_default(),
+                version.id
+            ),
+            pinned: file.pinned,
+        };
+        out.push((info, version));
+    }
+    out.sort_by_key(|(info, _)| info.file_name.to_lowercase());
+    Ok(out)
+}
+
+pub async fn check_content_updates_impl(
+    app: &AppHandle,
+    instance_id: String,
+    kind: Option<String>,
+) -> AppResult<Vec<ContentUpdateInfo>> {
+    if let Some(k) = kind.as_deref() {
+        if !CONTENT_KINDS.contains(&k) {
+            return Err("Tipo inválido (mods/resourcepacks/shaderpacks)".to_string().into());
+        }
+    }
+    let updates = find_updates(app, &instance_id, kind.as_deref()).await?;
+    Ok(updates.into_iter().map(|(info, _)| info).collect())
+}
+
+fn disk_name(clean_name: &str, enabled: bool) -> String {
+    if enabled {
+        clean_name.to_string()
+    } else {
+        format!("{}.disabled", clean_name)
+    }
+}
+
+/// Actualiza los archivos elegidos. Los reemplazados quedan en `.launcher/replaced/<fecha>/`.
+pub async fn apply_content_updates_impl(
+    app: &AppHandle,
+    instance_id: String,
+    file_names: Vec<String>,
+) -> AppResult<ContentUpdateResult> {
+    if file_names.is_empty() {
+        return Ok(ContentUpdateResult {
+            updated: Vec::new(),
+            failed: Vec::new(),
+            pinned: Vec::new(),
+            skipped: Vec::new(),
+            replaced_dir: None,
+        });
+    }
+    let base = instance_dir(app, &instance_id);
+    let (pinned, selected): (Vec<_>, Vec<_>) = find_updates(app, &instance_id, None)
+        .await?
+        .into_iter()
+        .filter(|(info, _)| file_names.contains(&info.file_name))
+        .partition(|(info, _)| info.pinned);
+    let pinned: Vec<String> = pinned.into_iter().map(|(info, _)| info.file_name).collect();
+    let skipped: Vec<String> = file_names
+        .iter()
+        .filter(|name| {
+            !pinned.contains(name) && !selected.iter().any(|(info, _)| &info.file_name == *name)
+        })
+        .cloned()
+        .collect();
+
+    let ts = chrono::Utc::now().format("%Y%m%d_%H%M%S").to_string();
+    let replaced_dir = base.join(".launcher").join("replaced").join(&ts);
+    let mut updated = Vec::new();
+    let mut failed = Vec::new();
+    let mut replacements: HashMap<(String, String), ModMetadataEntry> = HashMap::new();
+
+    for (info, version) in selected {
+        let (url, new_name, size, sha1) = match pick_primary_file(&version) {
+            Some(file) => file,
+            None => {
+                failed.push(info.file_name);
+                continue;
+            }
+        };
+        let dir = base.join(&info.kind);
+        let old_path = dir.join(disk_name(&info.file_name, info.enabled));
+        let backup_dir = replaced_dir.join(&info.kind);
+        let backup_path = backup_dir.join(disk_name(&info.file_name, info.enabled));
+        if tokio_fs::create_dir_all(&backup_dir).await.is_err()
+            || tokio_fs::rename(&old_path, &backup_path).await.is_err()
+        {
+            failed.push(info.file_name);
+            continue;
+        }
+
+        let new_path = dir.join(disk_name(new_name, info.enabled));
+        if let Err(e) = crate::downloader::download_file_checked(url, &new_path, size, sha1).await {
+            let _ = tokio_fs::remove_file(&new_path).await;
+            let _ = tokio_fs::rename(&backup_path, &old_path).await;
+            let _ = append_action_log(
+                app,
+                &format!(
+                    "content_update_failed instance={} file={} error={}",
+                    instance_id, info.file_name, e
+                ),
+            )
+            .await;
+            failed.push(info.file_name);
+            continue;
+        }
+
+        let dependencies = if info.kind == "mods" {
+            version
+                .dependencies
+                .iter()
+                .filter(|d| d.dependency_type == "required")
+                .filter_map(|d| d.version_id.clone().or_else(|| d.project_id.clone()))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        replacements.insert(
+            (info.kind.clone(), info.file_name.clone()),
+            ModMetadataEntry {
+                file_name: new_name.to_string(),
+                version_id: Some(version.id.clone()),
+                project_id: version.project_id.clone().or(info.project_id.clone()),
+                dependencies,
+                source: Some("modrinth".to_string()),
+                kind: Some(info.kind.clone()),
+                pinned: false,
+            },
+        );
+        updated.push(info.file_name);
+    }
+
+    if !replacements.is_empty() {
+        let mut metadata = load_mods_metadata(app, &instance_id).await;
+        metadata.retain(|m| {
+            !CONTENT_KINDS.iter().any(|k| {
+                metadata_kind_matches(&m.kind, k)
+                    && replacements.contains_key(&(k.to_string(), m.file_name.clone()))
+            })
+        });
+        metadata.extend(replacements.into_values());
+        save_mods_metadata(app, &instance_id, &metadata).await;
+        let _ = refresh_instance_mods_cache(app, &instance_id).await;
+    }
+
+    let _ = append_action_log(
+        app,
+        &format!(
+            "content_update instance={} updated={} failed={} pinned={} skipped={} replaced={}",
+            instance_id,
+            updated.len(),
+            failed.len(),
+            pinned.len(),
+            skipped.len(),
+            replaced_dir.to_string_lossy()
+        ),
+    )
+    .await;
+    let replaced_dir =
+        if updated.is_empty() { None } else { Some(replaced_dir.to_string_lossy().to_string()) };
+    Ok(ContentUpdateResult { updated, failed, pinned, skipped, replaced_dir })
+}
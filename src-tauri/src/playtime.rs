@@ -0,0 +1,109 @@
+use crate::models::{InstancePlaytime, PlaySession};
+use crate::utils::get_launcher_dir;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+use tokio::fs as tokio_fs;
+
+use crate::error::AppResult;
+fn instance_dir(app: &AppHandle, instance_id: &str) -> PathBuf {
+    get_launcher_dir(app).join("instances").join(instance_id)
+}
+
+fn sessions_path(app: &AppHandle, instance_id: &str) -> PathBuf {
+    instance_dir(app, instance_id).join(".launcher").join("sessions.json")
+}
+
+fn now_millis() -> i64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as i64).unwrap_or(0)
+}
+
+fn parse_sessions(raw: &str) -> Vec<PlaySession> {
+    serde_json::from_str::<Vec<PlaySession>>(raw.trim_start_matches('\u{feff}')).unwrap_or_default()
+}
+
+// Busca un crash report escrito durante la sesion (mtime posterior al inicio).
+fn find_session_crash_report(game_dir: &Path, started_at: i64) -> Option<String> {
+    let entries = fs::read_dir(game_dir.join("crash-reports")).ok()?;
+    let mut newest: Option<(String, i64)> = None;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+        let modified = entry
+            .metadata()
+            .ok()
+            .and_then(|m| m.modified().ok())
+            .and_then(|m| m.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|d| d.as_millis() as i64)
+            .unwrap_or(0);
+        if modified < started_at {
+            continue;
+        }
+        if newest.as_ref().map(|(_, ts)| modified > *ts).unwrap_or(true) {
+            newest = Some((entry.file_name().to_string_lossy().to_string(), modified));
+        }
+    }
+    newest.map(|(name, _)| name)
+}
+
+// Se llama desde el hilo que espera al proceso del juego, por eso es sincrono.
+pub(crate) fn record_play_session(
+    app: &AppHandle,
+    instance_id: &str,
+    game_dir: &Path,
+    version: &str,
+    started_at: i64,
+    exit_code: Option<i32>,
+) -> AppResult<PlaySession> {
+    let ended_at = now_millis();
+    let crash_report = find_session_crash_report(game_dir, started_at);
+    let session = PlaySession {
+        started_at,
+        ended_at,
+        duration_ms: (ended_at - started_at).max(0),
+        exit_code,
+        crashed: crash_report.is_some(),
+        crash_report,
+        version: Some(version.to_string()),
+    };
+
+    let path = sessions_path(app, instance_id);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| crate::error::AppError::Message(e.to_string()))?;
+    }
+    let mut sessions =
+        fs::read_to_string(&path).map(|raw| parse_sessions(&raw)).unwrap_or_default();
+    sessions.push(session.clone());
+    let raw = serde_json::to_string_pretty(&sessions)
+        .map_err(|e| crate::error::AppError::Message(e.to_string()))?;
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, raw).map_err(|e| crate::error::AppError::Message(e.to_string()))?;
+    fs::rename(&tmp, &path).map_err(|e| crate::error::AppError::Message(e.to_string()))?;
+    Ok(session)
+}
+
+pub async fn get_instance_playtime_impl(
+    app: &AppHandle,
+    instance_id: String,
+    limit: Option<usize>,
+) -> AppResult<InstancePlaytime> {
+    if !instance_dir(app, &instance_id).exists() {
+        return Err("La instancia no existe".to_string().into());
+    }
+    let mut sessions = match tokio_fs::read_to_string(sessions_path(app, &instance_id)).await {
+        Ok(raw) => parse_sessions(&raw),
+        Err(_) => Vec::new(),
+    };
+    let total_ms: i64 = sessions.iter().map(|s| s.duration_ms).sum();
+    let session_count = sessions.len() as u32;
+    let crash_count = sessions.iter().filter(|s| s.crashed).count() as u32;
+
+    sessions.sort_by_key(|s| std::cmp::Reverse(s.started_at));
+    if let Some(limit) = limit {
+        sessions.truncate(limit);
+    }
+    Ok(InstancePlaytime { instance_id, total_ms, session_count, crash_count, sessions })
+}
@@ -0,0 +1,68 @@
+mod export;
+mod import;
+
+use std::collections::HashMap;
+
+pub use export::export_prism_instance_impl;
+pub use import::import_prism_instance_impl;
+
+const UID_MINECRAFT: &str = "net.minecraft";
+const UID_FABRIC: &str = "net.fabricmc.fabric-loader";
+const UID_FORGE: &str = "net.minecraftforge";
+const UID_NEOFORGE: &str = "net.neoforged";
+const UID_QUILT: &str = "org.quiltmc.quilt-loader";
+const UID_INTERMEDIARY: &str = "net.fabricmc.intermediary";
+
+// instance.cfg usa el formato INI de QSettings: valores con comillas y escapes opcionales.
+fn unquote_cfg_value(raw: &str) -> String {
+    let trimmed = raw.trim();
+    let inner = match trimmed.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+        Some(inner) => inner,
+        None => return trimmed.to_string(),
+    };
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('n') => out.push('\n'),
+                Some('t') => out.push('\t'),
+                Some(other) => out.push(other),
+                None => {}
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+fn quote_cfg_value(value: &str) -> String {
+    let needs_quotes = value.is_empty()
+        || value.starts_with(' ')
+        || value.ends_with(' ')
+        || value.contains(['"', '\\', ';', ',', '=', '#']);
+    if !needs_quotes {
+        return value.to_string();
+    }
+    let escaped = value.replace('\\', "\\\\").replace('"', "\\\"");
+    format!("\"{}\"", escaped)
+}
+
+fn parse_instance_cfg(raw: &str) -> HashMap<String, String> {
+    let mut out = HashMap::new();
+    for line in raw.trim_start_matches('\u{feff}').lines() {
+        let line = line.trim();
+        if line.is_empty()
+            || line.starts_with('[')
+            || line.starts_with('#')
+            || line.starts_with(';')
+        {
+            continue;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            out.insert(key.trim().to_string(), unquote_cfg_value(value));
+        }
+    }
+    out
+}
@@ -0,0 +1,139 @@
+use crate::error::{AppError, AppResult};
+use crate::instances::get_instance_impl;
+use crate::models::{Instance, MmcComponent, MmcPack};
+use crate::modrinth::export::{add_overrides_to_zip, parse_loader_version, sanitize_pack_name};
+use crate::utils::{append_action_log, get_launcher_dir};
+use std::io::Write;
+use std::path::PathBuf;
+use tauri::AppHandle;
+use tokio::fs as tokio_fs;
+use zip::write::FileOptions;
+
+use super::{
+    quote_cfg_value, UID_FABRIC, UID_FORGE, UID_INTERMEDIARY, UID_MINECRAFT, UID_NEOFORGE,
+};
+
+fn resolve_export_path(
+    app: &AppHandle,
+    inst: &Instance,
+    dest_path: Option<String>,
+) -> AppResult<PathBuf> {
+    let safe_name = sanitize_pack_name(&inst.name);
+    let ts = chrono::Utc::now().format("%Y%m%d_%H%M%S");
+    if let Some(path) = dest_path {
+        let mut target = PathBuf::from(path);
+        if target.exists() && target.is_dir() {
+            target = target.join(format!("{}_{}.zip", safe_name, ts));
+        } else if target.extension().and_then(|s| s.to_str()).map(|s| s.to_lowercase())
+            != Some("zip".to_string())
+        {
+            target.set_extension("zip");
+        }
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| AppError::Message(e.to_string()))?;
+        }
+        return Ok(target);
+    }
+
+    let export_dir = get_launcher_dir(app).join("exports").join("prism");
+    std::fs::create_dir_all(&export_dir).map_err(|e| AppError::Message(e.to_string()))?;
+    Ok(export_dir.join(format!("{}_{}.zip", safe_name, ts)))
+}
+
+fn component(uid: &str, version: &str, important: bool) -> MmcComponent {
+    MmcComponent {
+        uid: uid.to_string(),
+        version: Some(version.to_string()),
+        cached_name: None,
+        important: if important { Some(true) } else { None },
+    }
+}
+
+fn build_mmc_pack(inst: &Instance) -> MmcPack {
+    let (mc_version, deps) = parse_loader_version(inst);
+    let mut components = vec![component(UID_MINECRAFT, &mc_version, true)];
+    if let Some(v) = deps.get("fabric-loader") {
+        components.push(component(UID_INTERMEDIARY, &mc_version, false));
+        components.push(component(UID_FABRIC, v, false));
+    }
+    if let Some(v) = deps.get("forge") {
+        components.push(component(UID_FORGE, v, false));
+    }
+    if let Some(v) = deps.get("neoforge") {
+        components.push(component(UID_NEOFORGE, v, false));
+    }
+    MmcPack { format_version: 1, components }
+}
+
+fn build_instance_cfg(inst: &Instance) -> String {
+    let mut lines = vec![
+        "[General]".to_string(),
+        "InstanceType=OneSix".to_string(),
+        format!("name={}", quote_cfg_value(&inst.name)),
+    ];
+    if let Some(settings) = inst.settings.as_ref() {
+        if let Some(memory) = settings.memory.as_ref() {
+            lines.push("OverrideMemory=true".to_string());
+            lines.push(format!("MinMemAlloc={}", memory.min_gb.max(1) * 1024));
+            lines.push(format!("MaxMemAlloc={}", memory.max_gb.max(1) * 1024));
+        }
+        if let Some(args) = settings.java_args.as_ref() {
+            lines.push("OverrideJavaArgs=true".to_string());
+            lines.push(format!("JvmArgs={}", quote_cfg_value(args)));
+        }
+        if let Some(resolution) = settings.resolution.as_ref() {
+            lines.push("OverrideWindow=true".to_string());
+            lines.push(format!("MinecraftWinWidth={}", resolution.width));
+            lines.push(format!("MinecraftWinHeight={}", resolution.height));
+        }
+    }
+    let mut out = lines.join("\n");
+    out.push('\n');
+    out
+}
+
+pub async fn export_prism_instance_impl(
+    app: &AppHandle,
+    instance_id: String,
+    dest_path: Option<String>,
+) -> AppResult<String> {
+    let inst = get_instance_impl(app, &instance_id).await?;
+    let instance_dir = get_launcher_dir(app).join("instances").join(&instance_id);
+    if !tokio_fs::try_exists(&instance_dir).await.unwrap_or(false) {
+        return Err("La instancia no existe".to_string().into());
+    }
+
+    let pack = build_mmc_pack(&inst);
+    let cfg = build_instance_cfg(&inst);
+    let app_handle = app.clone();
+
+    let zip_path = tokio::task::spawn_blocking(move || -> AppResult<PathBuf> {
+        let zip_path = resolve_export_path(&app_handle, &inst, dest_path)?;
+        let file =
+            std::fs::File::create(&zip_path).map_err(|e| AppError::Message(e.to_string()))?;
+        let mut zip = zip::ZipWriter::new(file);
+        let options = FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+        let pack_raw =
+            serde_json::to_vec_pretty(&pack).map_err(|e| AppError::Message(e.to_string()))?;
+        zip.start_file("mmc-pack.json", options).map_err(|e| AppError::Message(e.to_string()))?;
+        zip.write_all(&pack_raw).map_err(|e| AppError::Message(e.to_string()))?;
+
+        zip.start_file("instance.cfg", options).map_err(|e| AppError::Message(e.to_string()))?;
+        zip.write_all(cfg.as_bytes()).map_err(|e| AppError::Message(e.to_string()))?;
+
+        add_overrides_to_zip(&mut zip, &instance_dir, &instance_dir, ".minecraft", options)?;
+
+        zip.finish().map_err(|e| AppError::Message(e.to_string()))?;
+        Ok(zip_path)
+    })
+    .await
+    .map_err(|e| AppError::Message(e.to_string()))??;
+
+    let _ = append_action_log(
+        app,
+        &format!("prism_export instance={} path={}", instance_id, zip_path.to_string_lossy()),
+    )
+    .await;
+    Ok(zip_path.to_string_lossy().to_string())
+}
@@ -0,0 +1,212 @@
+use crate::error::{AppError, AppResult};
+use crate::instances::{
+    create_instance_impl, discard_instance, install_loader, refresh_instance_mods_cache,
+    update_instance_impl,
+};
+use crate::models::{
+    InstanceCreateRequest, InstanceSettings, InstanceSummary, InstanceUpdateRequest,
+    MemorySettings, MmcPack, ProgressPayload, Resolution, VersionManifest, VersionMetadata,
+};
+use crate::utils::{append_action_log, copy_dir_all, get_launcher_dir};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter};
+use tokio::fs as tokio_fs;
+
+use super::{parse_instance_cfg, UID_FABRIC, UID_FORGE, UID_MINECRAFT, UID_NEOFORGE, UID_QUILT};
+
+struct PackComponents {
+    mc_version: String,
+    loader: &'static str,
+    loader_version: Option<String>,
+}
+
+fn resolve_pack_components(pack: &MmcPack) -> AppResult<PackComponents> {
+    let version_of = |uid: &str| -> Option<String> {
+        pack.components
+            .iter()
+            .find(|c| c.uid == uid)
+            .and_then(|c| c.version.clone())
+            .filter(|v| !v.trim().is_empty())
+    };
+    if pack.components.iter().any(|c| c.uid == UID_QUILT) {
+        return Err("Quilt no esta soportado aun".to_string().into());
+    }
+    let mc_version = version_of(UID_MINECRAFT).ok_or_else(|| {
+        AppError::Message("mmc-pack.json no indica version de Minecraft".to_string())
+    })?;
+
+    let (loader, loader_version) = if let Some(v) = version_of(UID_NEOFORGE) {
+        ("neoforge", Some(v))
+    } else if let Some(v) = version_of(UID_FORGE) {
+        ("forge", Some(v))
+    } else if let Some(v) = version_of(UID_FABRIC) {
+        ("fabric", Some(v))
+    } else {
+        ("vanilla", None)
+    };
+    Ok(PackComponents { mc_version, loader, loader_version })
+}
+
+fn resolve_source_game_dir(instance_path: &Path) -> Option<PathBuf> {
+    [".minecraft", "minecraft"].iter().map(|d| instance_path.join(d)).find(|p| p.is_dir())
+}
+
+fn cfg_flag(cfg: &HashMap<String, String>, key: &str) -> bool {
+    cfg.get(key).map(|v| v.trim().eq_ignore_ascii_case("true")).unwrap_or(false)
+}
+
+fn cfg_u32(cfg: &HashMap<String, String>, key: &str) -> Option<u32> {
+    cfg.get(key).and_then(|v| v.trim().parse::<u32>().ok())
+}
+
+fn settings_from_cfg(cfg: &HashMap<String, String>) -> InstanceSettings {
+    let mut settings = InstanceSettings::default();
+
+    if cfg_flag(cfg, "OverrideMemory") {
+        // Prism guarda la memoria en MB; el launcher trabaja en GB.
+        let max_mb = cfg_u32(cfg, "MaxMemAlloc").unwrap_or(2048);
+        let min_mb = cfg_u32(cfg, "MinMemAlloc").unwrap_or(512);
+        let max_gb = max_mb.div_ceil(1024).max(1);
+        let min_gb = (min_mb / 1024).clamp(1, max_gb);
+        settings.memory = Some(MemorySettings { min_gb, max_gb });
+    }
+
+    let override_java = cfg_flag(cfg, "OverrideJava");
+    if override_java || cfg_flag(cfg, "OverrideJavaArgs") {
+        settings.java_args = cfg.get("JvmArgs").cloned().filter(|a| !a.trim().is_empty());
+    }
+    if override_java || cfg_flag(cfg, "OverrideJavaLocation") {
+        // Solo se conserva la ruta si existe en esta maquina.
+        settings.java_path =
+            cfg.get("JavaPath").cloned().filter(|p| !p.trim().is_empty() && Path::new(p).exists());
+    }
+
+    if cfg_flag(cfg, "OverrideWindow") {
+        let width = cfg_u32(cfg, "MinecraftWinWidth").unwrap_or(0);
+        let height = cfg_u32(cfg, "MinecraftWinHeight").unwrap_or(0);
+        if width > 0 && height > 0 {
+            settings.resolution = Some(Resolution { width, height });
+        }
+    }
+    settings
+}
+
+pub async fn import_prism_instance_impl(
+    app: &AppHandle,
+    path: String,
+    name: Option<String>,
+    manifest_cache: &Mutex<Option<VersionManifest>>,
+    metadata_cache: &Mutex<Option<VersionMetadata>>,
+) -> AppResult<InstanceSummary> {
+    let source = PathBuf::from(path.trim());
+    if !source.is_dir() {
+        return Err("La carpeta de la instancia no existe".to_string().into());
+    }
+    let pack_path = source.join("mmc-pack.json");
+    if !pack_path.exists() {
+        return Err("No se encontro mmc-pack.json (no es una instancia Prism/MultiMC)"
+            .to_string()
+            .into());
+    }
+    let game_dir = resolve_source_game_dir(&source)
+        .ok_or_else(|| AppError::Message("La instancia no tiene carpeta .minecraft".to_string()))?;
+
+    let pack_raw =
+        tokio_fs::read_to_string(&pack_path).await.map_err(|e| AppError::Message(e.to_string()))?;
+    let pack: MmcPack = serde_json::from_str(pack_raw.trim_start_matches('\u{feff}'))
+        .map_err(|e| AppError::Message(format!("mmc-pack.json invalido: {}", e)))?;
+    let cfg = match tokio_fs::read_to_string(source.join("instance.cfg")).await {
+        Ok(raw) => parse_instance_cfg(&raw),
+        Err(_) => HashMap::new(),
+    };
+
+    let components = resolve_pack_components(&pack)?;
+    let _ = app.emit(
+        "download-progress",
+        ProgressPayload { task: "Importando instancia Prism...".to_string(), percent: 0.0 },
+    );
+    let resolved_version = install_loader(
+        app,
+        components.loader,
+        components.mc_version.clone(),
+        components.loader_version.clone(),
+        manifest_cache,
+        metadata_cache,
+    )
+    .await?;
+
+    let folder_name = source.file_name().and_then(|s| s.to_str()).unwrap_or("Prism").to_string();
+    let instance_name = name
+        .map(|n| n.trim().to_string())
+        .filter(|n| !n.is_empty())
+        .or_else(|| cfg.get("name").map(|n| n.trim().to_string()).filter(|n| !n.is_empty()))
+        .unwrap_or(folder_name);
+
+    let req = InstanceCreateRequest {
+        name: instance_name,
+        version: resolved_version,
+        loader: components.loader.to_string(),
+        thumbnail: None,
+        tags: Some(vec!["prism".to_string()]),
+    };
+    let created = create_instance_impl(app, req)
+        .await
+        .map_err(|e| AppError::Message(format!("Crear instancia: {}", e)))?;
+
+    let _ = app.emit(
+        "download-progress",
+        ProgressPayload { task: "Copiando archivos del juego...".to_string(), percent: 80.0 },
+    );
+    let dest = get_launcher_dir(app).join("instances").join(&created.id);
+    let game_dir_clone = game_dir.clone();
+    let copied = tokio::task::spawn_blocking(move || copy_dir_all(&game_dir_clone, &dest))
+        .await
+        .map_err(|e| AppError::Message(e.to_string()))
+        .and_then(|r| r);
+    let copied = match copied {
+        Ok(count) => count,
+        Err(err) => {
+            let _ = discard_instance(app, &created.id).await;
+            return Err(format!("Copiar archivos de la instancia: {}", err).into());
+        }
+    };
+
+    let settings = settings_from_cfg(&cfg);
+    let summary = if settings.is_empty() {
+        created
+    } else {
+        let req = InstanceUpdateRequest {
+            name: None,
+            thumbnail: None,
+            tags: None,
+            settings: Some(settings),
+        };
+        match update_instance_impl(app, created.id.clone(), req).await {
+            Ok(summary) => summary,
+            Err(err) => {
+                let _ = discard_instance(app, &created.id).await;
+                return Err(format!("Aplicar ajustes de la instancia: {}", err).into());
+            }
+        }
+    };
+
+    let _ = refresh_instance_mods_cache(app, &summary.id).await;
+    let _ = app.emit(
+        "download-progress",
+        ProgressPayload { task: "Instancia importada".to_string(), percent: 100.0 },
+    );
+    let _ = append_action_log(
+        app,
+        &format!(
+            "prism_import instance={} source={} loader={} files={}",
+            summary.id,
+            source.to_string_lossy(),
+            components.loader,
+            copied
+        ),
+    )
+    .await;
+    Ok(summary)
+}
@@ -2,7 +2,7 @@ use crate::downloader::{
     download_client_impl, download_game_files_impl, download_libraries_for_version_impl,
     get_version_metadata_impl,
 };
-use crate::instances::get_instance_impl;
+use crate::instances::{get_instance_impl, repair_shared_folders};
 use crate::models::{VersionManifest, VersionMetadata};
 use crate::utils::get_launcher_dir;
 use std::sync::Mutex;
@@ -52,6 +52,11 @@ pub async fn repair_instance_impl(
     let _ = download_client_impl(app, base_version.clone(), metadata_cache).await?;
     let _ = download_game_files_impl(app, base_version.clone(), metadata_cache).await?;
     let _ = download_libraries_for_version_impl(app, inst.version.clone()).await?;
+    let relinked = repair_shared_folders(app, &instance_id).await?;
 
-    Ok("Repair completado: assets, cliente y librerias verificados.".to_string())
+    let mut message = "Repair completado: assets, cliente y librerias verificados.".to_string();
+    if relinked > 0 {
+        message.push_str(&format!(" {} carpetas compartidas reenlazadas.", relinked));
+    }
+    Ok(message)
 }
@@ -1,5 +1,7 @@
 use std::sync::Mutex;
 
+use crate::instances::InstanceStore;
+use crate::launcher::{GameLogBuffer, GameProcessRegistry};
 use crate::models::{MinecraftProfile, VersionManifest, VersionMetadata};
 use discord_rich_presence::DiscordIpcClient;
 
@@ -9,6 +11,9 @@ pub struct AppState {
     pub metadata_cache: Mutex<Option<VersionMetadata>>,
     pub current_profile: Mutex<Option<MinecraftProfile>>,
     pub discord_client: Mutex<Option<DiscordIpcClient>>,
+    pub instances: InstanceStore,
+    pub game_logs: GameLogBuffer,
+    pub processes: GameProcessRegistry,
 }
 
 impl AppState {
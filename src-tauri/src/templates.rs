@@ -0,0 +1,372 @@
+use crate::content::{load_mods_metadata, metadata_kind_matches};
+use crate::error::{AppError, AppResult};
+use crate::instances::{
+    create_instance_impl, get_instance_impl, install_loader, refresh_instance_mods_cache,
+};
+use crate::models::{
+    InstanceCreateRequest, InstanceTemplate, ProgressPayload, TemplateCreateRequest,
+    TemplateCreateResult, TemplateProject, TemplateSaveRequest, VersionManifest, VersionMetadata,
+};
+use crate::modrinth::export::parse_loader_version;
+use crate::modrinth::{modrinth_install_version_impl, modrinth_list_versions_impl};
+use crate::utils::{append_action_log, get_launcher_dir};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter};
+use tokio::fs as tokio_fs;
+use uuid::Uuid;
+
+// Los configs se guardan como texto dentro del JSON; algo mas grande no es un config.
+const MAX_CONFIG_FILE_BYTES: u64 = 256 * 1024;
+
+fn templates_dir(app: &AppHandle) -> PathBuf {
+    get_launcher_dir(app).join("templates")
+}
+
+fn template_path(app: &AppHandle, template_id: &str) -> AppResult<PathBuf> {
+    if template_id.is_empty() || !template_id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    {
+        return Err("Identificador de plantilla invalido".to_string().into());
+    }
+    Ok(templates_dir(app).join(format!("{}.json", template_id)))
+}
+
+fn now_millis() -> i64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as i64).unwrap_or(0)
+}
+
+fn parse_options(raw: &str) -> BTreeMap<String, String> {
+    raw.lines()
+        .filter_map(|line| line.split_once(':'))
+        .map(|(k, v)| (k.trim().to_string(), v.to_string()))
+        .filter(|(k, _)| !k.is_empty())
+        .collect()
+}
+
+fn collect_config_files(base: &Path, dir: &Path, out: &mut BTreeMap<String, String>) {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let ft = match entry.file_type() {
+            Ok(ft) => ft,
+            Err(_) => continue,
+        };
+        if ft.is_dir() {
+            collect_config_files(base, &path, out);
+            continue;
+        }
+        if !ft.is_file() {
+            continue;
+        }
+        if entry.metadata().map(|m| m.len() > MAX_CONFIG_FILE_BYTES).unwrap_or(true) {
+            continue;
+        }
+        let rel = match path.strip_prefix(base) {
+            Ok(rel) => rel.to_string_lossy().replace('\\', "/"),
+            Err(_) => continue,
+        };
+        // Solo texto: los binarios no sobreviven al JSON.
+        if let Ok(content) = std::fs::read_to_string(&path) {
+            out.insert(rel, content);
+        }
+    }
+}
+
+fn is_safe_relative(rel: &str) -> bool {
+    let path = Path::new(rel);
+    !rel.is_empty() && path.components().all(|c| matches!(c, std::path::Component::Normal(_)))
+}
+
+async fn write_template(app: &AppHandle, template: &InstanceTemplate) -> AppResult<()> {
+    let path = template_path(app, &template.id)?;
+    tokio_fs::create_dir_all(templates_dir(app))
+        .await
+        .map_err(|e| AppError::Message(e.to_string()))?;
+    let raw =
+        serde_json::to_string_pretty(template).map_err(|e| AppError::Message(e.to_string()))?;
+    let tmp = path.with_extension("tmp");
+    tokio_fs::write(&tmp, raw).await.map_err(|e| AppError::Message(e.to_string()))?;
+    tokio_fs::rename(&tmp, &path).await.map_err(|e| AppError::Message(e.to_string()))
+}
+
+async fn read_template(app: &AppHandle, template_id: &str) -> AppResult<InstanceTemplate> {
+    let path = template_path(app, template_id)?;
+    let raw = tokio_fs::read_to_string(&path)
+        .await
+        .map_err(|_| AppError::Message("La plantilla no existe".to_string()))?;
+    serde_json::from_str(raw.trim_start_matches('\u{feff}'))
+        .map_err(|e| AppError::Message(format!("Plantilla invalida: {}", e)))
+}
+
+pub async fn list_instance_templates_impl(app: &AppHandle) -> AppResult<Vec<InstanceTemplate>> {
+    let mut out = Vec::new();
+    let mut rd = match tokio_fs::read_dir(templates_dir(app)).await {
+        Ok(rd) => rd,
+        Err(_) => return Ok(out),
+    };
+    while let Ok(Some(entry)) = rd.next_entry().await {
+        let path = entry.path();
+        if path.extension().and_then(|s| s.to_str()) != Some("json") {
+            continue;
+        }
+        if let Ok(raw) = tokio_fs::read_to_string(&path).await {
+            if let Ok(template) =
+                serde_json::from_str::<InstanceTemplate>(raw.trim_start_matches('\u{feff}'))
+            {
+                out.push(template);
+            }
+        }
+    }
+    out.sort_by_key(|t| t.name.to_lowercase());
+    Ok(out)
+}
+
+pub async fn save_instance_template_impl(
+    app: &AppHandle,
+    instance_id: String,
+    req: TemplateSaveRequest,
+) -> AppResult<InstanceTemplate> {
+    let name = req.name.trim().to_string();
+    if name.is_empty() {
+        return Err("La plantilla necesita un nombre".to_string().into());
+    }
+    let inst = get_instance_impl(app, &instance_id).await?;
+    let game_dir = get_launcher_dir(app).join("instances").join(&instance_id);
+    let (mc_version, deps) = parse_loader_version(&inst);
+    let loader_version = match inst.loader.as_str() {
+        "fabric" => deps.get("fabric-loader").cloned(),
+        "forge" => deps.get("forge").cloned(),
+        "neoforge" => deps.get("neoforge").cloned(),
+        _ => None,
+    };
+
+    let mut projects: Vec<TemplateProject> = Vec::new();
+    for entry in load_mods_metadata(app, &instance_id).await {
+        let project_id = match entry.project_id {
+            Some(id) if entry.source.as_deref() == Some("modrinth") => id,
+            _ => continue,
+        };
+        let kind = ["mods", "resourcepacks", "shaderpacks"]
+            .into_iter()
+            .find(|k| metadata_kind_matches(&entry.kind, k));
+        if let Some(kind) = kind {
+            if !projects.iter().any(|p| p.project_id == project_id) {
+                projects.push(TemplateProject { project_id, kind: kind.to_string() });
+            }
+        }
+    }
+
+    let mut options = match tokio_fs::read_to_string(game_dir.join("options.txt")).await {
+        Ok(raw) => parse_options(&raw),
+        Err(_) => BTreeMap::new(),
+    };
+    if let Some(keys) = req.option_keys.as_ref() {
+        options.retain(|k, _| keys.iter().any(|wanted| wanted == k));
+    }
+
+    let config_dir = game_dir.join("config");
+    let mut config_files = tokio::task::spawn_blocking(move || {
+        let mut out = BTreeMap::new();
+        collect_config_files(&config_dir, &config_dir, &mut out);
+        out
+    })
+    .await
+    .map_err(|e| AppError::Message(e.to_string()))?;
+    if let Some(wanted) = req.config_files.as_ref() {
+        config_files.retain(|rel, _| wanted.iter().any(|w| w.replace('\\', "/") == *rel));
+    }
+
+    let template = InstanceTemplate {
+        id: Uuid::new_v4().to_string(),
+        name,
+        loader: inst.loader.clone(),
+        mc_version,
+        loader_version,
+        projects,
+        options,
+        config_files,
+        created_at: now_millis(),
+    };
+    write_template(app, &template).await?;
+    let _ = append_action_log(
+        app,
+        &format!(
+            "template_save template={} instance={} projects={}",
+            template.id,
+            instance_id,
+            template.projects.len()
+        ),
+    )
+    .await;
+    Ok(template)
+}
+
+pub async fn delete_instance_template_impl(app: &AppHandle, template_id: String) -> AppResult<()> {
+    let path = template_path(app, &template_id)?;
+    if !tokio_fs::try_exists(&path).await.unwrap_or(false) {
+        return Err("La plantilla no existe".to_string().into());
+    }
+    tokio_fs::remove_file(&path).await.map_err(|e| AppError::Message(e.to_string()))
+}
+
+async fn apply_template_files(game_dir: &Path, template: &InstanceTemplate) -> AppResult<()> {
+    if !template.options.is_empty() {
+        let options_path = game_dir.join("options.txt");
+        let mut options = match tokio_fs::read_to_string(&options_path).await {
+            Ok(raw) => parse_options(&raw),
+            Err(_) => BTreeMap::new(),
+        };
+        options.extend(template.options.clone());
+        let mut raw: String = options.iter().map(|(k, v)| format!("{}:{}\n", k, v)).collect();
+        if raw.is_empty() {
+            raw.push('\n');
+        }
+        tokio_fs::write(&options_path, raw).await.map_err(|e| AppError::Message(e.to_string()))?;
+    }
+    let config_dir = game_dir.join("config");
+    for (rel, content) in template.config_files.iter() {
+        if !is_safe_relative(rel) {
+            continue;
+        }
+        let dest = config_dir.join(rel);
+        if let Some(parent) = dest.parent() {
+            tokio_fs::create_dir_all(parent).await.map_err(|e| AppError::Message(e.to_string()))?;
+        }
+        tokio_fs::write(&dest, content).await.map_err(|e| AppError::Message(e.to_string()))?;
+    }
+    Ok(())
+}
+
+fn project_type_for_kind(kind: &str) -> &'static str {
+    match kind {
+        "resourcepacks" => "resourcepack",
+        "shaderpacks" => "shader",
+        _ => "mod",
+    }
+}
+
+pub async fn create_instance_from_template_impl(
+    app: &AppHandle,
+    template_id: String,
+    req: TemplateCreateRequest,
+    manifest_cache: &Mutex<Option<VersionManifest>>,
+    metadata_cache: &Mutex<Option<VersionMetadata>>,
+) -> AppResult<TemplateCreateResult> {
+    let template = read_template(app, &template_id).await?;
+    let mc_version = req
+        .mc_version
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .unwrap_or_else(|| template.mc_version.clone());
+    // La version del loader de la plantilla solo vale para su version de Minecraft.
+    let loader_version =
+        req.loader_version.map(|v| v.trim().to_string()).filter(|v| !v.is_empty()).or_else(|| {
+            if mc_version == template.mc_version {
+                template.loader_version.clone()
+            } else {
+                None
+            }
+        });
+
+    let _ = app.emit(
+        "download-progress",
+        ProgressPayload { task: format!("Preparando {}...", template.name), percent: 0.0 },
+    );
+    let resolved_version = install_loader(
+        app,
+        &template.loader,
+        mc_version.clone(),
+        loader_version,
+        manifest_cache,
+        metadata_cache,
+    )
+    .await?;
+
+    let created = create_instance_impl(
+        app,
+        InstanceCreateRequest {
+            name: req.name,
+            version: resolved_version,
+            loader: template.loader.clone(),
+            thumbnail: None,
+            tags: Some(vec!["template".to_string()]),
+        },
+    )
+    .await?;
+    let game_dir = get_launcher_dir(app).join("instances").join(&created.id);
+
+    let modrinth_loader = match template.loader.as_str() {
+        "fabric" | "forge" | "neoforge" => Some(template.loader.clone()),
+        _ => None,
+    };
+    let mut installed = Vec::new();
+    let mut skipped = Vec::new();
+    let total = template.projects.len().max(1) as f64;
+    for (idx, project) in template.projects.iter().enumerate() {
+        let _ = app.emit(
+            "download-progress",
+            ProgressPayload {
+                task: format!("Instalando {}...", project.project_id),
+                percent: (idx as f64 / total) * 100.0,
+            },
+        );
+        // Solo los mods se filtran por loader; packs y shaders usan otros "loaders" en Modrinth.
+        let loader_filter = if project.kind == "mods" { modrinth_loader.clone() } else { None };
+        let versions = modrinth_list_versions_impl(
+            app,
+            project.project_id.clone(),
+            loader_filter.clone(),
+            Some(mc_version.clone()),
+        )
+        .await
+        .unwrap_or_default();
+        let version = match versions.first() {
+            Some(v) => v,
+            None => {
+                skipped.push(project.project_id.clone());
+                continue;
+            }
+        };
+        let result = modrinth_install_version_impl(
+            app,
+            created.id.clone(),
+            version.id.clone(),
+            loader_filter,
+            Some(mc_version.clone()),
+            Some(project_type_for_kind(&project.kind).to_string()),
+        )
+        .await;
+        match result {
+            Ok(_) => installed.push(project.project_id.clone()),
+            Err(_) => skipped.push(project.project_id.clone()),
+        }
+    }
+
+    apply_template_files(&game_dir, &template).await?;
+    let mut instance = created;
+    if let Ok(count) = refresh_instance_mods_cache(app, &instance.id).await {
+        instance.mods_count = count;
+    }
+
+    let _ = app.emit(
+        "download-progress",
+        ProgressPayload { task: "Instancia creada".to_string(), percent: 100.0 },
+    );
+    let _ = append_action_log(
+        app,
+        &format!(
+            "template_create template={} instance={} installed={} skipped={}",
+            template.id,
+            instance.id,
+            installed.len(),
+            skipped.len()
+        ),
+    )
+    .await;
+    Ok(TemplateCreateResult { instance, installed, skipped })
+}
@@ -269,7 +269,7 @@ pub fn prune_old_backups(dir: &Path, keep: usize) -> AppResult<()> {
         let modified = meta.modified().unwrap_or(std::time::SystemTime::UNIX_EPOCH);
         entries.push((path, modified));
     }
-    entries.sort_by(|a, b| b.1.cmp(&a.1));
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.1));
     if entries.len() <= keep {
         return Ok(());
     }
@@ -278,3 +278,21 @@ pub fn prune_old_backups(dir: &Path, keep: usize) -> AppResult<()> {
     }
     Ok(())
 }
+
+pub fn copy_dir_all(src: &Path, dest: &Path) -> AppResult<u64> {
+    fs::create_dir_all(dest).map_err(|e| crate::error::AppError::Message(e.to_string()))?;
+    let mut copied = 0u64;
+    for entry in fs::read_dir(src).map_err(|e| crate::error::AppError::Message(e.to_string()))? {
+        let entry = entry.map_err(|e| crate::error::AppError::Message(e.to_string()))?;
+        let path = entry.path();
+        let target = dest.join(entry.file_name());
+        let ft = entry.file_type().map_err(|e| crate::error::AppError::Message(e.to_string()))?;
+        if ft.is_dir() {
+            copied += copy_dir_all(&path, &target)?;
+        } else if ft.is_file() {
+            fs::copy(&path, &target).map_err(|e| crate::error::AppError::Message(e.to_string()))?;
+            copied += 1;
+        }
+    }
+    Ok(copied)
+}
@@ -57,7 +57,7 @@ pub async fn list_instance_worlds_impl(
         out.push((name, modified));
     }
 
-    out.sort_by(|a, b| b.1.cmp(&a.1));
+    out.sort_by_key(|entry| std::cmp::Reverse(entry.1));
     Ok(out.into_iter().map(|(name, _)| name).collect())
 }
 
@@ -1,4 +1,8 @@
-import type { InstanceContentItem, InstanceLogEntry } from "../../types";
+import type {
+  ContentDependencyReport,
+  InstanceContentItem,
+  InstanceLogEntry,
+} from "../../types";
 import { invokeTyped } from "./core";
 
 export function listInstanceContent(
@@ -12,17 +16,45 @@ export function toggleInstanceContent(
   instanceId: string,
   kind: string,
   fileName: string,
-  enabled: boolean
-): Promise<void> {
-  return invokeTyped("toggle_instance_content", { instanceId, kind, fileName, enabled });
+  enabled: boolean,
+  cascade?: boolean,
+  removeOrphans?: boolean
+): Promise<string[]> {
+  return invokeTyped("toggle_instance_content", {
+    instanceId,
+    kind,
+    fileName,
+    enabled,
+    cascade,
+    removeOrphans,
+  });
 }
 
 export function deleteInstanceContent(
   instanceId: string,
   kind: string,
+  fileName: string,
+  cascade?: boolean,
+  removeOrphans?: boolean
+): Promise<string[]> {
+  return invokeTyped("delete_instance_content", {
+    instanceId,
+    kind,
+    fileName,
+    cascade,
+    removeOrphans,
+  });
+}
+
+export function previewContentDependencies(
+  instanceId: string,
   fileName: string
-): Promise<void> {
-  return invokeTyped("delete_instance_content", { instanceId, kind, fileName });
+): Promise<ContentDependencyReport> {
+  return invokeTyped("preview_content_dependencies", { instanceId, fileName });
+}
+
+export function listOrphanedMods(instanceId: string): Promise<string[]> {
+  return invokeTyped("list_orphaned_mods", { instanceId });
 }
 
 export function openInstanceContentFolder(instanceId: string, kind: string): Promise<void> {
@@ -56,6 +56,7 @@ export interface GameSettings {
   javaArgs: string;
   javaPath: string;
   maxFps: number;
+  launchLogsKeep?: number;
   focusMode: boolean;
   performanceOverlay: boolean;
 }
@@ -85,6 +86,14 @@ export interface InstanceContentItem {
   source: string | null;
   project_id: string | null;
   version_id: string | null;
+  pinned?: boolean;
+}
+
+export interface ContentDependencyReport {
+  file_name: string;
+  dependents: string[];
+  orphans: string[];
+  disabled_dependencies: string[];
 }
 
 export interface InstanceLogEntry {
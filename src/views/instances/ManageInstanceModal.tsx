@@ -57,8 +57,24 @@ export function ManageInstanceModal({
   });
 
   const handleToggle = async (item: InstanceContentItem) => {
+    const enabling = !item.enabled;
     try {
-      await tauri.toggleInstanceContent(instance.id, tab, item.file_name, !item.enabled);
+      let cascade: boolean | undefined;
+      if (tab === "mods") {
+        const report = await tauri.previewContentDependencies(instance.id, item.file_name);
+        const affected = enabling ? report.disabled_dependencies : report.dependents;
+        if (affected.length) {
+          cascade = await onConfirm({
+            title: enabling ? "Activar dependencias" : "Desactivar dependientes",
+            message: enabling
+              ? `${item.name} necesita: ${affected.join(", ")}. Activarlos tambien?`
+              : `${item.name} es requerido por: ${affected.join(", ")}. Desactivarlos tambien?`,
+            confirmLabel: enabling ? "Activar todos" : "Desactivar todos",
+            cancelLabel: "Solo este",
+          });
+        }
+      }
+      await tauri.toggleInstanceContent(instance.id, tab, item.file_name, enabling, cascade);
       await reloadContent();
     } catch (e) {
       setStatus({ message: String(e), kind: "error" });
@@ -66,7 +82,21 @@ export function ManageInstanceModal({
   };
 
   const handleDelete = async (item: InstanceContentItem) => {
-    const extra = item.required_by.length ? ` Requerido por: ${item.required_by.join(", ")}` : "";
+    let extra = "";
+    if (tab === "mods") {
+      try {
+        const report = await tauri.previewContentDependencies(instance.id, item.file_name);
+        if (report.dependents.length) {
+          extra += ` Tambien se eliminaran: ${report.dependents.join(", ")}.`;
+        }
+        if (report.orphans.length) {
+          extra += ` Librerias sin uso que se eliminaran: ${report.orphans.join(", ")}.`;
+        }
+      } catch (e) {
+        setStatus({ message: String(e), kind: "error" });
+        return;
+      }
+    }
     const ok = await onConfirm({
       title: "Eliminar contenido",
       message: `Eliminar ${item.name}?${extra}`,
@@ -76,7 +106,7 @@ export function ManageInstanceModal({
     });
     if (!ok) return;
     try {
-      await tauri.deleteInstanceContent(instance.id, tab, item.file_name);
+      await tauri.deleteInstanceContent(instance.id, tab, item.file_name, true, true);
       await reloadContent();
     } catch (e) {
       setStatus({ message: String(e), kind: "error" });
@@ -90,7 +120,8 @@ export function ManageInstanceModal({
     try {
       const targets = filteredItems.filter((item) => item.enabled !== enabled);
       for (const item of targets) {
-        await tauri.toggleInstanceContent(instance.id, tab, item.file_name, enabled);
+        // El lote ya decide que se toca; no se arrastran dependencias fuera de la vista.
+        await tauri.toggleInstanceContent(instance.id, tab, item.file_name, enabled, false);
       }
       await reloadContent();
       setStatus({